@@ -0,0 +1,285 @@
+// User settings from `habit_tracker.toml` in the working directory, next to
+// habits.json. Every section and key is optional; the REPL, the single-command
+// CLI and the dashboard all read the same settings through `current()`.
+
+use crate::i18n::{self, t, Locale};
+use crate::render;
+use crate::theme::{Theme, Thresholds};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+
+pub const CONFIG_FILE: &str = "habit_tracker.toml";
+
+// Keys accepted by `config get/set/unset`.
+pub const KEYS: &[&str] = &[
+    "data_file", "locale", "week_start", "date_format", "progress_bar_width", "export_file",
+    "calendar_file", "thresholds.on_track", "thresholds.close", "theme.preset", "theme.colors.<role>",
+];
+
+static CURRENT: LazyLock<RwLock<Config>> = LazyLock::new(|| RwLock::new(Config::default()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub data_file: String,
+    pub locale: String,      // a code from i18n::LOCALES, or "auto" for the environment's
+    pub week_start: WeekStart,
+    pub date_format: String, // strftime pattern for dates shown on screen, or "auto" for the locale's
+    pub progress_bar_width: usize,
+    pub export_file: String, // used by `export` without a filename
+    pub calendar_file: String, // .ics file rewritten on every save, or empty for none
+    pub thresholds: Thresholds,
+    pub theme: ThemeConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            data_file: "habits.json".to_string(),
+            locale: "auto".to_string(),
+            week_start: WeekStart::Monday,
+            date_format: "auto".to_string(),
+            progress_bar_width: 10,
+            export_file: "habits_export.csv".to_string(),
+            calendar_file: String::new(),
+            thresholds: Thresholds::default(),
+            theme: ThemeConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub preset: String,
+    pub colors: BTreeMap<String, String>, // role name -> style
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        ThemeConfig { preset: "default".to_string(), colors: BTreeMap::new() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum WeekStart {
+    Monday,
+    Sunday,
+    Saturday,
+}
+
+impl WeekStart {
+    pub fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+            WeekStart::Saturday => Weekday::Sat,
+        }
+    }
+
+    // Position of `day` in a week that starts on this day, from 0.
+    pub fn offset(self, day: Weekday) -> u32 {
+        (day.num_days_from_monday() + 7 - self.weekday().num_days_from_monday()) % 7
+    }
+
+    // The first day of the week holding `date`.
+    pub fn week_of(self, date: NaiveDate) -> NaiveDate {
+        date - chrono::Duration::days(self.offset(date.weekday()) as i64)
+    }
+
+    // The seven days of the week in display order.
+    pub fn days(self) -> Vec<Weekday> {
+        let mut day = self.weekday();
+        (0..7).map(|_| {
+            let current = day;
+            day = day.succ();
+            current
+        }).collect()
+    }
+}
+
+impl FromStr for WeekStart {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "monday" | "mon" => Ok(WeekStart::Monday),
+            "sunday" | "sun" => Ok(WeekStart::Sunday),
+            "saturday" | "sat" => Ok(WeekStart::Saturday),
+            _ => Err(t!("config.unknown_week_start", value = s)),
+        }
+    }
+}
+
+impl TryFrom<String> for WeekStart {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+impl From<WeekStart> for String {
+    fn from(start: WeekStart) -> String {
+        match start {
+            WeekStart::Monday => "monday",
+            WeekStart::Sunday => "sunday",
+            WeekStart::Saturday => "saturday",
+        }.to_string()
+    }
+}
+
+pub fn current() -> Config {
+    CURRENT.read().unwrap().clone()
+}
+
+// Makes `config` the settings everything else reads.
+pub fn apply(config: Config) -> Result<(), String> {
+    render::set_theme(config.theme()?, config.thresholds);
+    i18n::set_locale(config.locale()?);
+    *CURRENT.write().unwrap() = config;
+    Ok(())
+}
+
+// Formats a date for display with the configured `date_format`.
+pub fn format_date(date: NaiveDate) -> String {
+    let pattern = CURRENT.read().unwrap().date_format.clone();
+    let pattern = if pattern == "auto" { t!("date.format") } else { pattern };
+    i18n::format_date(date, &pattern)
+}
+
+impl Config {
+    // A missing file means every setting has its default.
+    pub fn load() -> Result<Config, String> {
+        let text = match fs::read_to_string(CONFIG_FILE) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(t!("config.read_failed", file = CONFIG_FILE, error = e)),
+        };
+        let config: Config = toml::from_str(&text)
+            .map_err(|e| format!("{}: {}", CONFIG_FILE, e.to_string().trim_end()))?;
+        config.validate().map_err(|e| format!("{}: {}", CONFIG_FILE, e))?;
+        Ok(config)
+    }
+
+    // Rewrites the whole file, so comments in it are not kept.
+    pub fn save(&self) -> Result<(), String> {
+        let text = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(CONFIG_FILE, text).map_err(|e| t!("config.write_failed", file = CONFIG_FILE, error = e))
+    }
+
+    pub fn theme(&self) -> Result<Theme, String> {
+        Theme::new(&self.theme.preset, &self.theme.colors)
+    }
+
+    pub fn locale(&self) -> Result<Locale, String> {
+        if self.locale.eq_ignore_ascii_case("auto") {
+            return Ok(Locale::detect());
+        }
+        Locale::from_code(&self.locale)
+            .ok_or_else(|| t!("config.unknown_locale", value = self.locale, locales = i18n::LOCALES.join(", ")))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.theme()?;
+        self.locale()?;
+        self.thresholds.validate()?;
+        if self.data_file.trim().is_empty() {
+            return Err(t!("config.empty", key = "data_file"));
+        }
+        if self.export_file.trim().is_empty() {
+            return Err(t!("config.empty", key = "export_file"));
+        }
+        if !(1..=60).contains(&self.progress_bar_width) {
+            return Err(t!("config.out_of_range", key = "progress_bar_width", min = 1, max = 60));
+        }
+        if self.date_format == "auto" {
+            return Ok(());
+        }
+        let mut items = StrftimeItems::new(&self.date_format).peekable();
+        if items.peek().is_none() || items.any(|item| item == Item::Error) {
+            return Err(t!("config.invalid_date_format", value = self.date_format));
+        }
+        Ok(())
+    }
+
+    // The value at a dotted key such as "thresholds.close".
+    pub fn get(&self, key: &str) -> Result<String, String> {
+        let root = toml::Value::try_from(self).map_err(|e| e.to_string())?;
+        let mut value = &root;
+        for part in key.split('.') {
+            value = match value.get(part) {
+                Some(value) => value,
+                None if is_color_key(key) => return Ok(t!("config.from_preset")),
+                None => return Err(unknown_key(key)),
+            };
+        }
+        Ok(match value {
+            toml::Value::String(s) => s.clone(),
+            toml::Value::Table(table) => {
+                let keys: Vec<String> = table.keys().map(|k| format!("{}.{}", key, k)).collect();
+                return Err(t!("config.section", key = key, keys = keys.join(", ")));
+            }
+            other => other.to_string(),
+        })
+    }
+
+    // A copy with `key` set to `value`, or an error if the key is unknown or
+    // the result doesn't validate.
+    pub fn set(&self, key: &str, value: &str) -> Result<Config, String> {
+        let (path, last) = key.rsplit_once('.').map_or(("", key), |(path, last)| (path, last));
+        let mut root = toml::Value::try_from(self).map_err(|e| e.to_string())?;
+        let table = table_at(&mut root, path).ok_or_else(|| unknown_key(key))?;
+        let new_value = match table.get(last) {
+            Some(toml::Value::Integer(_)) => toml::Value::Integer(
+                value.parse().map_err(|_| t!("config.not_a_number", key = key))?,
+            ),
+            Some(toml::Value::Table(_)) => return Err(t!("config.set_section", key = key)),
+            Some(_) => toml::Value::String(value.to_string()),
+            None if is_color_key(key) => toml::Value::String(value.to_string()),
+            None => return Err(unknown_key(key)),
+        };
+        table.insert(last.to_string(), new_value);
+        Config::from_value(root)
+    }
+
+    // A copy with `key` back at its default. Color overrides are removed.
+    pub fn unset(&self, key: &str) -> Result<Config, String> {
+        if is_color_key(key) {
+            let mut config = self.clone();
+            config.theme.colors.remove(&key["theme.colors.".len()..].to_lowercase());
+            return Ok(config);
+        }
+        let default = Config::default().get(key)?;
+        self.set(key, &default)
+    }
+
+    fn from_value(root: toml::Value) -> Result<Config, String> {
+        let config: Config = root.try_into().map_err(|e: toml::de::Error| e.message().to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+fn table_at<'a>(root: &'a mut toml::Value, path: &str) -> Option<&'a mut toml::Table> {
+    let mut value = root;
+    for part in path.split('.').filter(|p| !p.is_empty()) {
+        value = value.get_mut(part)?;
+    }
+    value.as_table_mut()
+}
+
+fn is_color_key(key: &str) -> bool {
+    key.strip_prefix("theme.colors.").is_some_and(|role| !role.is_empty() && !role.contains('.'))
+}
+
+fn unknown_key(key: &str) -> String {
+    t!("config.unknown_key", key = key, keys = KEYS.join(", "))
+}
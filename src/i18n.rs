@@ -0,0 +1,372 @@
+// Translations for everything shown to the user. Messages live in TOML
+// catalogs under `locales/`, one per language, compiled into the binary.
+// English is the reference catalog: a key missing elsewhere falls back to it.
+//
+// `t!("habit.added", name = habit.name, id = id)` looks up a message and
+// fills in its `{name}` and `{id}` placeholders. `tn!` picks the `.one` or
+// `.other` form of a message by a count, which is also available as `{count}`.
+// Machine-readable output (JSON, TSV, CSV) is not translated.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{LazyLock, RwLock};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Locale {
+    En,
+    De,
+}
+
+// Codes accepted by the `locale` setting, besides "auto".
+pub const LOCALES: &[&str] = &["en", "de"];
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+static CATALOGS: LazyLock<HashMap<&'static str, Catalog>> = LazyLock::new(|| {
+    Locale::ALL.iter().map(|l| (l.code(), parse_catalog(l.source()))).collect()
+});
+
+type Catalog = HashMap<String, String>;
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    // Takes a plain code or a POSIX locale name such as "de_AT.UTF-8".
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['_', '-', '.', '@']).next().unwrap_or("").to_lowercase();
+        Locale::ALL.into_iter().find(|l| l.code() == language)
+    }
+
+    // From LC_ALL, LC_MESSAGES or LANG, like other command-line programs.
+    // Unset and unsupported locales mean English.
+    pub fn detect() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_code(&value))
+            .unwrap_or(Locale::En)
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.toml"),
+            Locale::De => include_str!("../locales/de.toml"),
+        }
+    }
+}
+
+// Nested tables become dotted keys: `[habit] added = ...` is "habit.added".
+fn parse_catalog(source: &str) -> Catalog {
+    fn flatten(prefix: &str, table: &toml::Table, out: &mut Catalog) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match value {
+                toml::Value::Table(table) => flatten(&key, table, out),
+                toml::Value::String(s) => {
+                    out.insert(key, s.clone());
+                }
+                other => panic!("translation '{}' must be a string, found {}", key, other.type_str()),
+            }
+        }
+    }
+    let table: toml::Table = toml::from_str(source).expect("translation catalogs are valid TOML");
+    let mut catalog = Catalog::new();
+    flatten("", &table, &mut catalog);
+    catalog
+}
+
+pub fn set_locale(locale: Locale) {
+    *LOCALE.write().unwrap() = locale;
+}
+
+pub fn locale() -> Locale {
+    *LOCALE.read().unwrap()
+}
+
+fn message(locale: Locale, key: &str) -> Option<&'static str> {
+    CATALOGS.get(locale.code())?.get(key).map(String::as_str)
+}
+
+pub fn translate(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate_in(locale(), key, args)
+}
+
+// Unknown keys come out as the key itself, which is easy to spot.
+pub fn translate_in(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = message(locale, key).or_else(|| message(Locale::En, key)).unwrap_or(key);
+    fill(template, args)
+}
+
+pub fn translate_plural(key: &str, count: u64, args: &[(&str, &dyn Display)]) -> String {
+    let mut args = args.to_vec();
+    args.push(("count", &count));
+    translate(&format!("{}.{}", key, plural_form(locale(), count)), &args)
+}
+
+// Both shipped languages have a singular for exactly one and a plural for
+// everything else, zero included.
+fn plural_form(locale: Locale, count: u64) -> &'static str {
+    match locale {
+        Locale::En | Locale::De => if count == 1 { "one" } else { "other" },
+    }
+}
+
+// Replaces `{name}` with the matching argument. Anything in braces that
+// isn't an argument is left alone.
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            args.iter().find(|(n, _)| *n == name).map(|(_, v)| (v.to_string(), end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(&value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate($key, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
+
+macro_rules! tn {
+    ($key:expr, $count:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::translate_plural($key, $count as u64, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),*])
+    };
+}
+
+pub(crate) use {t, tn};
+
+fn weekday_key(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "mon",
+        Weekday::Tue => "tue",
+        Weekday::Wed => "wed",
+        Weekday::Thu => "thu",
+        Weekday::Fri => "fri",
+        Weekday::Sat => "sat",
+        Weekday::Sun => "sun",
+    }
+}
+
+const MONTH_KEYS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+// "Monday"
+pub fn weekday_name(day: Weekday) -> String {
+    weekday_name_in(locale(), day)
+}
+
+fn weekday_name_in(locale: Locale, day: Weekday) -> String {
+    translate_in(locale, &format!("date.weekday.{}", weekday_key(day)), &[])
+}
+
+// Two letters for calendar column headers, like "Mo".
+pub fn weekday_initials(day: Weekday) -> String {
+    translate(&format!("date.weekday_initials.{}", weekday_key(day)), &[])
+}
+
+// "January"; `month` counts from 1.
+pub fn month_name(month: u32) -> String {
+    month_name_in(locale(), month)
+}
+
+fn month_name_in(locale: Locale, month: u32) -> String {
+    translate_in(locale, &format!("date.month.{}", MONTH_KEYS[month as usize - 1]), &[])
+}
+
+// Formats like chrono's strftime, except that the names from %A, %a, %B,
+// %b and %h come from the catalog.
+pub fn format_date(date: NaiveDate, pattern: &str) -> String {
+    format_date_in(locale(), date, pattern)
+}
+
+fn format_date_in(locale: Locale, date: NaiveDate, pattern: &str) -> String {
+    let abbreviation = |key: String| translate_in(locale, &key, &[]);
+    let mut localized = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            localized.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('A') => localized.push_str(&weekday_name_in(locale, date.weekday())),
+            Some('a') => localized.push_str(&abbreviation(format!("date.weekday_short.{}", weekday_key(date.weekday())))),
+            Some('B') => localized.push_str(&month_name_in(locale, date.month())),
+            Some('b') | Some('h') => localized.push_str(&abbreviation(format!("date.month_short.{}", MONTH_KEYS[date.month0() as usize]))),
+            Some(other) => {
+                localized.push('%');
+                localized.push(other);
+            }
+            None => localized.push('%'),
+        }
+    }
+    date.format(&localized).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    // Source files that look up messages, searched for `t!`/`tn!` keys.
+    const SOURCES: &[(&str, &str)] = &[
+        ("main.rs", include_str!("main.rs")),
+        ("config.rs", include_str!("config.rs")),
+        ("ics.rs", include_str!("ics.rs")),
+        ("import.rs", include_str!("import.rs")),
+        ("loop_habits.rs", include_str!("loop_habits.rs")),
+        ("output.rs", include_str!("output.rs")),
+        ("parser.rs", include_str!("parser.rs")),
+        ("report.rs", include_str!("report.rs")),
+        ("tag_expr.rs", include_str!("tag_expr.rs")),
+        ("theme.rs", include_str!("theme.rs")),
+        ("tui.rs", include_str!("tui.rs")),
+    ];
+
+    fn catalog(locale: Locale) -> &'static Catalog {
+        &CATALOGS[locale.code()]
+    }
+
+    fn placeholders(message: &str) -> BTreeSet<String> {
+        message.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+            .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .collect()
+    }
+
+    // String literals that follow `pattern` in the source, such as the keys
+    // in `t!("key"`.
+    fn used_keys(source: &str, pattern: &str) -> Vec<String> {
+        source.match_indices(pattern)
+            .filter(|(i, _)| !source[..*i].ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+            .map(|(i, _)| {
+                let rest = &source[i + pattern.len()..];
+                rest[..rest.find('"').unwrap()].to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_locale_translates_every_key() {
+        let english: BTreeSet<&String> = catalog(Locale::En).keys().collect();
+        for locale in Locale::ALL {
+            let keys: BTreeSet<&String> = catalog(locale).keys().collect();
+            let missing: Vec<_> = english.difference(&keys).collect();
+            let extra: Vec<_> = keys.difference(&english).collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", locale.code(), missing);
+            assert!(extra.is_empty(), "{} has keys English doesn't: {:?}", locale.code(), extra);
+        }
+    }
+
+    #[test]
+    fn translations_are_not_empty() {
+        for locale in Locale::ALL {
+            for (key, message) in catalog(locale) {
+                assert!(!message.trim().is_empty(), "{}: '{}' is empty", locale.code(), key);
+            }
+        }
+    }
+
+    #[test]
+    fn translations_keep_the_placeholders() {
+        for locale in Locale::ALL {
+            for (key, message) in catalog(locale) {
+                let english = &catalog(Locale::En)[key];
+                assert_eq!(placeholders(message), placeholders(english), "{}: '{}'", locale.code(), key);
+            }
+        }
+    }
+
+    #[test]
+    fn every_key_in_the_source_exists() {
+        let english = catalog(Locale::En);
+        for (file, source) in SOURCES {
+            // Help lines and dashboard fields name their keys outside the macro.
+            let keys = ["t!(\"", "field(\"", "section(\"", "\"help."].iter()
+                .flat_map(|pattern| used_keys(source, pattern).into_iter().map(move |key| match *pattern {
+                    "\"help." => format!("help.{}", key),
+                    _ => key,
+                }));
+            for key in keys {
+                assert!(english.contains_key(&key), "{}: unknown message '{}'", file, key);
+            }
+            for key in used_keys(source, "tn!(\"") {
+                for form in ["one", "other"] {
+                    let key = format!("{}.{}", key, form);
+                    assert!(english.contains_key(&key), "{}: unknown message '{}'", file, key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_locale_names_the_days_and_months() {
+        for locale in Locale::ALL {
+            for day in ["mon", "tue", "wed", "thu", "fri", "sat", "sun"] {
+                for kind in ["weekday", "weekday_short", "weekday_initials"] {
+                    let key = format!("date.{}.{}", kind, day);
+                    assert!(message(locale, &key).is_some(), "{}: missing '{}'", locale.code(), key);
+                }
+                let initials = message(locale, &format!("date.weekday_initials.{}", day)).unwrap();
+                assert_eq!(initials.chars().count(), 2, "calendar columns are two characters wide");
+            }
+            for month in MONTH_KEYS {
+                for kind in ["month", "month_short"] {
+                    let key = format!("date.{}.{}", kind, month);
+                    assert!(message(locale, &key).is_some(), "{}: missing '{}'", locale.code(), key);
+                }
+            }
+            assert!(message(locale, "date.format").is_some());
+        }
+    }
+
+    #[test]
+    fn placeholders_are_filled_by_name() {
+        let name = "Run";
+        assert_eq!(fill("'{name}' at {time}", &[("time", &"07:00"), ("name", &name)]), "'Run' at 07:00");
+        assert_eq!(fill("{unknown} {name}", &[("name", &name)]), "{unknown} Run");
+        assert_eq!(fill("{ {", &[]), "{ {");
+    }
+
+    #[test]
+    fn dates_use_the_locale_names() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        assert_eq!(format_date_in(Locale::En, date, "%A, %d %B %Y"), "Monday, 03 March 2025");
+        assert_eq!(format_date_in(Locale::De, date, "%A, %d. %B %Y"), "Montag, 03. März 2025");
+        assert_eq!(format_date_in(Locale::De, date, "%a %b %%"), "Mo. März %");
+    }
+
+    #[test]
+    fn posix_locale_names_are_recognized() {
+        assert_eq!(Locale::from_code("de_AT.UTF-8"), Some(Locale::De));
+        assert_eq!(Locale::from_code("en-GB"), Some(Locale::En));
+        assert_eq!(Locale::from_code("C"), None);
+    }
+}
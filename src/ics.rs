@@ -0,0 +1,214 @@
+// iCalendar (RFC 5545) files for calendar apps. Every completion becomes an
+// all-day event, and every reminder a repeating event with an alarm on the
+// days the habit's goal calls for. UIDs come from habit IDs and dates, so a
+// calendar subscribed to the file updates its events when the file is
+// written again rather than adding them twice.
+
+use crate::config::{self, WeekStart};
+use crate::i18n::t;
+use crate::{GoalType, Habit, HabitTracker};
+use chrono::{Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+use std::fs;
+use std::io;
+
+// Reminder events are this long; calendars need some length to show them.
+const REMINDER_MINUTES: u32 = 15;
+
+pub fn write(tracker: &HabitTracker, path: &str) -> io::Result<()> {
+    fs::write(path, calendar(tracker))
+}
+
+fn calendar(tracker: &HabitTracker) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//habit_tracker//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape(&t!("ics.calendar_name"))),
+        // Asks subscribed calendars to look for changes every hour.
+        "REFRESH-INTERVAL;VALUE=DURATION:PT1H".to_string(),
+        "X-PUBLISHED-TTL:PT1H".to_string(),
+    ];
+    let mut habits: Vec<&Habit> = tracker.habits.values().collect();
+    habits.sort_by(|a, b| a.id.cmp(&b.id));
+    for habit in habits {
+        for &date in &habit.completions {
+            lines.extend(completion_event(habit, date, &stamp));
+        }
+        lines.extend(reminder_events(habit, &stamp));
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+fn completion_event(habit: &Habit, date: NaiveDate, stamp: &str) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}-{}@habit-tracker", habit.id, date.format("%Y%m%d")),
+        format!("DTSTAMP:{}", stamp),
+        format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+        format!("DTEND;VALUE=DATE:{}", (date + chrono::Duration::days(1)).format("%Y%m%d")),
+        format!("SUMMARY:{}", escape(&t!("ics.done", name = habit.name))),
+        format!("DESCRIPTION:{}", escape(&progress(habit, date))),
+    ];
+    if let Some(category) = &habit.category {
+        lines.push(format!("CATEGORIES:{}", escape(category)));
+    }
+    lines.extend(["TRANSP:TRANSPARENT".to_string(), "END:VEVENT".to_string()]);
+    lines
+}
+
+// How far the goal in force on `date` had come by the end of that day.
+fn progress(habit: &Habit, date: NaiveDate) -> String {
+    let goal = habit.goal_on(date);
+    let count_from = |first: NaiveDate| habit.completions.iter().filter(|&&d| d >= first && d <= date).count();
+    match goal {
+        GoalType::Daily => t!("ics.goal", goal = goal.display()),
+        GoalType::Weekly(n) => {
+            let first = config::current().week_start.week_of(date);
+            t!("ics.week_progress", goal = goal.display(), count = count_from(first), target = n)
+        }
+        GoalType::Monthly(n) => {
+            let first = date.with_day(1).unwrap_or(date);
+            t!("ics.month_progress", goal = goal.display(), count = count_from(first), target = n)
+        }
+    }
+}
+
+// One repeating event per stretch of the habit's goal history, each ending
+// where the next goal takes over, and the last one when the habit was
+// archived. Paused days are left out.
+fn reminder_events(habit: &Habit, stamp: &str) -> Vec<String> {
+    let Some(time) = habit.reminder_time.as_deref().and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok()) else {
+        return Vec::new();
+    };
+    let week_start = config::current().week_start;
+    let mut stretches: Vec<(NaiveDate, &GoalType)> = habit.goal_history.iter().map(|c| (c.from, &c.goal)).collect();
+    if stretches.is_empty() {
+        stretches.push((habit.created_date, &habit.goal));
+    }
+    let datetime = |date: NaiveDate| date.and_time(time).format("%Y%m%dT%H%M%S").to_string();
+
+    let mut lines = Vec::new();
+    for (i, &(from, goal)) in stretches.iter().enumerate() {
+        let until = stretches.get(i + 1).map(|&(next, _)| next - chrono::Duration::days(1));
+        let until = match (until, habit.archived_date) {
+            (Some(until), Some(archived)) => Some(until.min(archived)),
+            (until, archived) => until.or(archived),
+        };
+        let due = |date: NaiveDate| is_due(goal, date, week_start);
+        let Some(first) = from.iter_days().take(31).find(|&d| due(d)) else {
+            continue;
+        };
+        if until.is_some_and(|until| until < first) {
+            continue;
+        }
+        let mut rule = recurrence(goal, week_start);
+        if let Some(until) = until {
+            rule.push_str(&format!(";UNTIL={}", datetime(until)));
+        }
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-reminder-{}@habit-tracker", habit.id, from.format("%Y%m%d")),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART:{}", datetime(first)),
+            format!("DURATION:PT{}M", REMINDER_MINUTES),
+            format!("RRULE:{}", rule),
+        ]);
+        let skipped: Vec<String> = habit.pauses.iter()
+            .flat_map(|p| p.from.iter_days().take_while(move |d| *d <= p.to))
+            .filter(|&d| d >= first && until.is_none_or(|until| d <= until) && due(d))
+            .map(datetime)
+            .collect();
+        if !skipped.is_empty() {
+            lines.push(format!("EXDATE:{}", skipped.join(",")));
+        }
+        lines.push(format!("SUMMARY:{}", escape(&t!("ics.reminder", name = habit.name))));
+        lines.push(format!("DESCRIPTION:{}", escape(&t!("ics.goal", goal = goal.display()))));
+        if let Some(category) = &habit.category {
+            lines.push(format!("CATEGORIES:{}", escape(category)));
+        }
+        lines.extend([
+            "TRANSP:TRANSPARENT".to_string(),
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".to_string(),
+            "TRIGGER:PT0S".to_string(),
+            format!("DESCRIPTION:{}", escape(&t!("reminder.due_body", name = habit.name))),
+            "END:VALARM".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines
+}
+
+// The days a reminder repeats on: every day for daily goals, and for fewer
+// times a week or month, that many days spread evenly over the week (from
+// `week_start`) or over the first 28 days of the month, which every month has.
+fn reminder_days(goal: &GoalType) -> Option<Vec<u32>> {
+    match *goal {
+        GoalType::Weekly(n) if n < 7 => Some((0..n.max(1)).map(|i| i * 7 / n.max(1)).collect()),
+        GoalType::Monthly(n) if n < 28 => Some((0..n.max(1)).map(|i| 1 + i * 28 / n.max(1)).collect()),
+        _ => None,
+    }
+}
+
+fn is_due(goal: &GoalType, date: NaiveDate, week_start: WeekStart) -> bool {
+    match (goal, reminder_days(goal)) {
+        (GoalType::Weekly(_), Some(days)) => days.contains(&week_start.offset(date.weekday())),
+        (GoalType::Monthly(_), Some(days)) => days.contains(&date.day()),
+        _ => true,
+    }
+}
+
+fn recurrence(goal: &GoalType, week_start: WeekStart) -> String {
+    match (goal, reminder_days(goal)) {
+        (GoalType::Weekly(_), Some(days)) => {
+            let weekdays = week_start.days();
+            let days: Vec<&str> = days.iter().map(|&i| weekday_code(weekdays[i as usize])).collect();
+            format!("FREQ=WEEKLY;WKST={};BYDAY={}", weekday_code(week_start.weekday()), days.join(","))
+        }
+        (GoalType::Monthly(_), Some(days)) => {
+            let days: Vec<String> = days.iter().map(u32::to_string).collect();
+            format!("FREQ=MONTHLY;BYMONTHDAY={}", days.join(","))
+        }
+        _ => "FREQ=DAILY".to_string(),
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Lines are at most 75 bytes; longer ones continue on lines starting with a
+// space. Every line ends in CRLF.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
@@ -0,0 +1,304 @@
+// Reading habits and completions from other files into the tracker: CSV with
+// a row per completion (as written by `export --mode completions`), JSON in
+// the same layout as habits.json, such as a backup, or a Loop Habit Tracker
+// backup (see loop_habits.rs).
+//
+// Nothing is changed until the whole file has been read and checked, and a
+// preview of what will happen to each habit is printed either way.
+
+use crate::i18n::{t, tn};
+use crate::render::{self, Paint, Role};
+use crate::loop_habits;
+use crate::{Category, GoalType, Habit, HabitTracker};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// What happens to a habit that's already tracked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    Merge,   // add the dates it doesn't have yet and fill in missing details
+    Skip,    // leave it as it is
+    Replace, // take the imported habit's completions, or the whole habit from JSON
+}
+
+impl Strategy {
+    pub fn from_flag(flag: Option<&str>) -> Result<Self, String> {
+        match flag.map(str::to_lowercase).as_deref() {
+            None | Some("merge") => Ok(Strategy::Merge),
+            Some("skip") => Ok(Strategy::Skip),
+            Some("replace") => Ok(Strategy::Replace),
+            Some(other) => Err(t!("import.unknown_strategy", strategy = other)),
+        }
+    }
+}
+
+// Habit fields a CSV column can be mapped to, with the header `export`
+// writes for each.
+const FIELDS: &[(&str, &str)] = &[
+    ("date", "Date"),
+    ("name", "Habit Name"),
+    ("id", "ID"),
+    ("category", "Category"),
+    ("goal", "Goal"),
+];
+
+// CSV header names for each field, matched case-insensitively.
+#[derive(Debug, Clone)]
+pub struct Mapping(BTreeMap<&'static str, String>);
+
+impl Mapping {
+    // Parses "date=Day,name=Habit" on top of the default headers.
+    pub fn from_flag(flag: Option<&str>) -> Result<Self, String> {
+        let mut columns: BTreeMap<&'static str, String> =
+            FIELDS.iter().map(|&(field, header)| (field, header.to_string())).collect();
+        for pair in flag.unwrap_or("").split(',').filter(|p| !p.trim().is_empty()) {
+            let (field, column) = pair.split_once('=').ok_or_else(|| t!("import.bad_mapping", value = pair))?;
+            let field = field.trim().to_lowercase();
+            let &(field, _) = FIELDS.iter().find(|(f, _)| *f == field)
+                .ok_or_else(|| t!("import.bad_mapping", value = pair))?;
+            columns.insert(field, column.trim().to_string());
+        }
+        Ok(Mapping(columns))
+    }
+
+    fn column(&self, headers: &csv::StringRecord, field: &str) -> Option<usize> {
+        headers.iter().position(|h| h.trim().eq_ignore_ascii_case(&self.0[field]))
+    }
+}
+
+// Habits and category settings read from a file, not yet in the tracker.
+struct Incoming {
+    habits: Vec<Habit>,
+    categories: BTreeMap<String, Category>,
+    complete: bool, // whole habit records (JSON) rather than only completions (CSV)
+    notes: Vec<String>, // what couldn't be converted
+}
+
+fn read(path: &str, mapping: &Mapping) -> Result<Incoming, String> {
+    let extension = Path::new(path).extension().and_then(|e| e.to_str()).map(str::to_lowercase);
+    let failed = |e: &dyn std::fmt::Display| t!("import.read_failed", file = path, error = e);
+    if loop_habits::is_backup(path) {
+        let backup = loop_habits::read(path)?;
+        return Ok(Incoming { habits: backup.habits, categories: BTreeMap::new(), complete: true, notes: backup.notes });
+    }
+    match extension.as_deref() {
+        Some("json") => {
+            let text = fs::read_to_string(path).map_err(|e| failed(&e))?;
+            let tracker: HabitTracker = serde_json::from_str(&text).map_err(|e| failed(&e))?;
+            let mut habits: Vec<Habit> = tracker.habits.into_values().collect();
+            habits.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(Incoming { habits, categories: tracker.categories, complete: true, notes: Vec::new() })
+        }
+        Some("csv") => {
+            let reader = csv::ReaderBuilder::new().flexible(true).from_path(path).map_err(|e| failed(&e))?;
+            read_csv(reader, mapping)
+        }
+        _ => Err(t!("import.unknown_format", file = path)),
+    }
+}
+
+fn read_csv<R: std::io::Read>(mut reader: csv::Reader<R>, mapping: &Mapping) -> Result<Incoming, String> {
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let required = |field: &str| {
+        mapping.column(&headers, field).ok_or_else(|| t!("import.missing_column",
+            column = mapping.0[field], field = field, columns = headers.iter().collect::<Vec<_>>().join(", ")))
+    };
+    let date_column = required("date")?;
+    let name_column = mapping.column(&headers, "name");
+    let id_column = mapping.column(&headers, "id");
+    if name_column.is_none() && id_column.is_none() {
+        required("name")?;
+    }
+    let category_column = mapping.column(&headers, "category");
+    let goal_column = mapping.column(&headers, "goal");
+
+    // Rows are grouped by ID when there is one and by name otherwise. The
+    // goal and category on a habit's latest row win.
+    let mut habits: BTreeMap<String, (Habit, NaiveDate)> = BTreeMap::new();
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 2; // the header is row 1
+        let record = record.map_err(|e| t!("import.row_error", row = row, error = e))?;
+        let cell = |column: Option<usize>| column.and_then(|c| record.get(c)).map(str::trim).filter(|v| !v.is_empty());
+
+        let date_text = cell(Some(date_column)).unwrap_or("");
+        let date = NaiveDate::parse_from_str(date_text, "%Y-%m-%d")
+            .map_err(|_| t!("import.row_error", row = row, error = t!("error.invalid_date", value = date_text)))?;
+        let id = cell(id_column).map(|id| id.trim_start_matches('#').to_string());
+        let Some(name) = cell(name_column).map(str::to_string).or_else(|| id.clone()) else {
+            return Err(t!("import.row_error", row = row, error = t!("import.no_name")));
+        };
+        let goal = match cell(goal_column) {
+            Some(text) => Some(GoalType::from_string(text)
+                .ok_or_else(|| t!("import.row_error", row = row, error = t!("error.invalid_goal")))?),
+            None => None,
+        };
+        let category = cell(category_column).map(str::to_string);
+
+        let key = id.clone().unwrap_or_else(|| name.clone());
+        let (habit, latest) = habits.entry(key).or_insert_with(|| {
+            let mut habit = Habit::new(id.unwrap_or_default(), name, GoalType::Daily, None);
+            habit.created_date = date;
+            (habit, date)
+        });
+        habit.mark_complete(date);
+        habit.created_date = habit.created_date.min(date);
+        if date >= *latest {
+            *latest = date;
+            habit.goal = goal.unwrap_or(habit.goal.clone());
+            habit.category = category.or(habit.category.take());
+        }
+    }
+
+    let mut habits: Vec<Habit> = habits.into_values().map(|(habit, _)| habit).collect();
+    habits.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(Incoming { habits, categories: BTreeMap::new(), complete: false, notes: Vec::new() })
+}
+
+enum Action {
+    Add,
+    Merge { new: usize, existing: usize },
+    Replace { before: usize },
+    Skip,
+}
+
+// What will happen to one imported habit.
+struct Change {
+    habit: Habit,
+    target: Option<String>, // ID of the tracked habit it matches
+    action: Action,
+}
+
+fn plan(tracker: &HabitTracker, incoming: Incoming, strategy: Strategy) -> Vec<Change> {
+    incoming.habits.into_iter().map(|habit| {
+        let target = tracker.find_exact(&habit.id)
+            .filter(|_| !habit.id.is_empty())
+            .or_else(|| tracker.find_exact(&habit.name));
+        let action = match (target, strategy) {
+            (None, _) => Action::Add,
+            (Some(_), Strategy::Skip) => Action::Skip,
+            (Some(current), Strategy::Replace) => Action::Replace { before: current.completions.len() },
+            (Some(current), Strategy::Merge) => {
+                let existing = habit.completions.iter().filter(|d| current.completions.contains(d)).count();
+                Action::Merge { new: habit.completions.len() - existing, existing }
+            }
+        };
+        Change { target: target.map(|h| h.id.clone()), habit, action }
+    }).collect()
+}
+
+fn apply(tracker: &mut HabitTracker, changes: Vec<Change>, complete: bool) {
+    for Change { mut habit, target, action } in changes {
+        match (action, target) {
+            (Action::Add, _) => {
+                if habit.id.is_empty() || tracker.habits.contains_key(&habit.id) {
+                    habit.id = tracker.unique_id(&habit.name);
+                }
+                tracker.habits.insert(habit.id.clone(), habit);
+            }
+            (Action::Replace { .. }, Some(id)) => {
+                let current = tracker.habits.get_mut(&id).unwrap();
+                if complete {
+                    habit.id = id;
+                    *current = habit;
+                } else {
+                    current.completions = habit.completions;
+                    current.created_date = current.created_date.min(habit.created_date);
+                }
+            }
+            (Action::Merge { .. }, Some(id)) => {
+                let current = tracker.habits.get_mut(&id).unwrap();
+                for date in habit.completions {
+                    current.mark_complete(date);
+                }
+                current.created_date = current.created_date.min(habit.created_date);
+                if current.category.is_none() {
+                    current.category = habit.category;
+                }
+                if complete {
+                    for tag in habit.tags {
+                        if !current.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                            current.tags.push(tag);
+                        }
+                    }
+                    if current.reminder_time.is_none() {
+                        current.reminder_time = habit.reminder_time;
+                    }
+                    for pause in habit.pauses {
+                        if !current.pauses.contains(&pause) {
+                            current.pauses.push(pause);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Reads `path`, prints what importing it would do and, unless `dry_run`,
+// does it. Returns whether the tracker changed.
+pub fn run(tracker: &mut HabitTracker, path: &str, strategy: Strategy, mapping: &Mapping, dry_run: bool) -> bool {
+    let mut incoming = match read(path, mapping) {
+        Ok(incoming) => incoming,
+        Err(msg) => {
+            crate::fail(format!("❌ {}", msg));
+            return false;
+        }
+    };
+    if incoming.habits.is_empty() {
+        println!("{}", t!("import.nothing", file = path).paint(Role::Info));
+        return false;
+    }
+
+    let new_categories: Vec<(String, Category)> = incoming.categories.iter()
+        .filter(|(name, _)| !tracker.categories.contains_key(*name))
+        .map(|(name, category)| (name.clone(), category.clone()))
+        .collect();
+    let complete = incoming.complete;
+    let notes = std::mem::take(&mut incoming.notes);
+    let changes = plan(tracker, incoming, strategy);
+
+    let total: usize = changes.iter().map(|c| c.habit.completions.len()).sum();
+    println!("\n{}", t!("import.title", file = path).paint(Role::Title));
+    let completions = tn!("import.completions", total);
+    println!("{}", tn!("import.summary", changes.len(), completions = completions).paint(Role::Muted));
+    let mut counts = BTreeMap::new();
+    for change in &changes {
+        let name = change.habit.name.paint(Role::Name);
+        let count = change.habit.completions.len();
+        let (kind, line) = match change.action {
+            Action::Add => ("added", tn!("import.added", count, name = name).paint(Role::Success)),
+            Action::Merge { new, existing } => ("merged", tn!("import.merged", new, name = name, existing = existing).paint(Role::Info)),
+            Action::Replace { before } => ("replaced", tn!("import.replaced", count, name = name, before = before).paint(Role::Warning)),
+            Action::Skip => ("skipped", t!("import.skipped", name = name).paint(Role::Muted)),
+        };
+        *counts.entry(kind).or_insert(0) += 1;
+        println!("  {}", line);
+    }
+    for (name, _) in &new_categories {
+        println!("  {}", t!("import.category", name = name).paint(Role::Success));
+    }
+    if !notes.is_empty() {
+        println!("\n{}", t!("import.not_converted").paint(Role::Warning));
+        for note in &notes {
+            println!("  {} {}", render::glyph("•", "-"), note);
+        }
+    }
+
+    if dry_run {
+        println!("\n{}", t!("import.dry_run").paint(Role::Warning));
+        return false;
+    }
+    let changed = changes.iter().any(|c| !matches!(c.action, Action::Skip | Action::Merge { new: 0, .. }))
+        || !new_categories.is_empty();
+    apply(tracker, changes, complete);
+    tracker.categories.extend(new_categories);
+    let count = |kind| counts.get(kind).copied().unwrap_or(0);
+    println!("\n{}", t!("import.done",
+        added = count("added"), merged = count("merged"), replaced = count("replaced"), skipped = count("skipped"))
+        .paint(Role::Celebrate));
+    changed
+}
+
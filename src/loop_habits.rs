@@ -0,0 +1,268 @@
+// Backups from Loop Habit Tracker for Android: the zip written by its
+// "Export as CSV" (or that zip unpacked into a folder), and the SQLite file
+// written by "Export full backup", which is read with the sqlite3 program.
+//
+// Loop knows more than this tracker does (scores, colors, numeric amounts,
+// reminders on some weekdays only), so whatever doesn't carry over is listed
+// as a note for the import report rather than dropped silently.
+
+use crate::i18n::{t, tn};
+use crate::{GoalType, Habit, PausePeriod};
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+// Checkmark values in Loop's files. A 1 marks a day Loop filled in itself
+// because the frequency was met, so only 2 counts as a completion. Numeric
+// habits store the amount times 1000 instead.
+const YES_MANUAL: f64 = 2.0;
+const SKIP: f64 = 3.0;
+
+// `reminder_days` with every weekday set.
+const EVERY_DAY: u32 = 0b111_1111;
+
+pub struct LoopBackup {
+    pub habits: Vec<Habit>,
+    pub notes: Vec<String>, // what couldn't be converted, for the report
+}
+
+// A habit as Loop describes it, before conversion.
+#[derive(Default)]
+struct LoopHabit {
+    name: String,
+    numerator: u32,   // times...
+    denominator: u32, // ...every this many days
+    numeric: bool,
+    unit: String,
+    archived: bool,
+    reminder: Option<(u32, u32, u32)>, // hour, minute, weekday bits
+    entries: Vec<(NaiveDate, f64)>,
+    notes: usize, // entries with a note attached
+}
+
+// A .db or .zip file, or a folder holding Loop's Habits.csv.
+pub fn is_backup(path: &str) -> bool {
+    let path = Path::new(path);
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_lowercase);
+    matches!(extension.as_deref(), Some("db" | "zip")) || path.join("Habits.csv").is_file()
+}
+
+pub fn read(path: &str) -> Result<LoopBackup, String> {
+    let failed = |e: &dyn std::fmt::Display| t!("import.read_failed", file = path, error = e);
+    let mut notes = vec![t!("loop.scores"), t!("loop.extras")];
+    let habits = if path.to_lowercase().ends_with(".db") {
+        read_database(path)?
+    } else {
+        let files = if Path::new(path).is_dir() {
+            read_folder(path).map_err(|e| failed(&e))?
+        } else {
+            read_zip(path).map_err(|e| failed(&e))?
+        };
+        notes.push(t!("loop.no_reminders"));
+        read_csv_export(&files)?
+    };
+    let habits = habits.into_iter().map(|h| convert(h, &mut notes)).collect();
+    Ok(LoopBackup { habits, notes })
+}
+
+// Every file in the zip, by its path inside it.
+fn read_zip(path: &str) -> Result<BTreeMap<String, String>, Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(path)?)?;
+    let mut files = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_file() {
+            let mut text = String::new();
+            file.read_to_string(&mut text)?;
+            files.insert(file.name().to_string(), text);
+        }
+    }
+    Ok(files)
+}
+
+// The CSV files in the folder and the folders inside it, by relative path.
+fn read_folder(path: &str) -> std::io::Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.file_type()?.is_dir() {
+            for inner in fs::read_dir(entry.path())? {
+                let inner = inner?;
+                files.insert(format!("{}/{}", name, inner.file_name().to_string_lossy()), fs::read_to_string(inner.path())?);
+            }
+        } else if name.ends_with(".csv") {
+            files.insert(name, fs::read_to_string(entry.path())?);
+        }
+    }
+    Ok(files)
+}
+
+// Habits.csv lists the habits with a 3-digit position, and a folder named
+// "<position> <name>" holds each one's Checkmarks.csv of "date,value" lines.
+// Older versions of Loop call the frequency columns NumRepetitions and
+// Interval.
+fn read_csv_export(files: &BTreeMap<String, String>) -> Result<Vec<LoopHabit>, String> {
+    let (prefix, habits_csv) = files.iter()
+        .find_map(|(name, text)| name.strip_suffix("Habits.csv").map(|prefix| (prefix, text)))
+        .ok_or_else(|| t!("loop.no_habits_csv"))?;
+    let mut reader = csv::Reader::from_reader(habits_csv.as_bytes());
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let column = |names: &[&str]| headers.iter().position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)));
+    let (position, name) = (column(&["Position"]), column(&["Name"]));
+    let numerator = column(&["FrequencyNumerator", "NumRepetitions"]);
+    let denominator = column(&["FrequencyDenominator", "Interval"]);
+    let (kind, unit, archived) = (column(&["Type"]), column(&["Unit"]), column(&["Archived?"]));
+
+    let mut habits = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        let cell = |column: Option<usize>| column.and_then(|c| record.get(c)).unwrap_or("").trim();
+        let folder = format!("{}{} ", prefix, cell(position));
+        let entries = files.iter()
+            .find(|(path, _)| path.starts_with(&folder) && path.ends_with("/Checkmarks.csv"))
+            .map_or_else(Vec::new, |(_, text)| {
+                text.lines().filter_map(|line| {
+                    let (date, value) = line.split_once(',')?;
+                    Some((NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?, value.trim().parse().ok()?))
+                }).collect()
+            });
+        habits.push(LoopHabit {
+            name: cell(name).to_string(),
+            numerator: cell(numerator).parse().unwrap_or(1),
+            denominator: cell(denominator).parse().unwrap_or(1),
+            numeric: cell(kind) == "1" || cell(kind).eq_ignore_ascii_case("numerical"),
+            unit: cell(unit).to_string(),
+            archived: cell(archived).eq_ignore_ascii_case("true"),
+            entries,
+            ..LoopHabit::default()
+        });
+    }
+    Ok(habits)
+}
+
+fn read_database(path: &str) -> Result<Vec<LoopHabit>, String> {
+    let habit_rows = query(path, "SELECT * FROM Habits ORDER BY position")?;
+    let mut habits: BTreeMap<String, LoopHabit> = BTreeMap::new();
+    let mut order = Vec::new();
+    for row in &habit_rows {
+        let number = |key: &str| row.get(key).and_then(|v| v.parse::<u32>().ok());
+        let reminder = number("reminder_hour")
+            .zip(number("reminder_min"))
+            .map(|(hour, minute)| (hour, minute, number("reminder_days").unwrap_or(EVERY_DAY)));
+        let id = row.get("id").cloned().unwrap_or_default();
+        order.push(id.clone());
+        habits.insert(id, LoopHabit {
+            name: row.get("name").cloned().unwrap_or_default(),
+            numerator: number("freq_num").unwrap_or(1),
+            denominator: number("freq_den").unwrap_or(1),
+            numeric: number("type") == Some(1),
+            unit: row.get("unit").cloned().unwrap_or_default(),
+            archived: number("archived") == Some(1),
+            reminder,
+            ..LoopHabit::default()
+        });
+    }
+    for row in query(path, "SELECT * FROM Repetitions ORDER BY timestamp")? {
+        let Some(habit) = row.get("habit").and_then(|id| habits.get_mut(id)) else {
+            continue;
+        };
+        // Timestamps are milliseconds at UTC midnight of the day.
+        let date = row.get("timestamp")
+            .and_then(|ms| ms.parse().ok())
+            .and_then(DateTime::from_timestamp_millis)
+            .map(|t| t.date_naive());
+        let value = row.get("value").and_then(|v| v.parse().ok());
+        if let (Some(date), Some(value)) = (date, value) {
+            habit.entries.push((date, value));
+        }
+        if row.get("notes").is_some_and(|n| !n.is_empty()) {
+            habit.notes += 1;
+        }
+    }
+    Ok(order.iter().filter_map(|id| habits.remove(id)).collect())
+}
+
+// Rows of a query on the backup as column -> value.
+fn query(path: &str, sql: &str) -> Result<Vec<BTreeMap<String, String>>, String> {
+    let output = Command::new("sqlite3")
+        .args(["-readonly", "-csv", "-header", path, sql])
+        .output()
+        .map_err(|_| t!("loop.no_sqlite"))?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(t!("import.read_failed", file = path, error = error));
+    }
+    let mut reader = csv::Reader::from_reader(output.stdout.as_slice());
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    reader.records()
+        .map(|record| {
+            let record = record.map_err(|e| e.to_string())?;
+            Ok(headers.iter().map(str::to_string).zip(record.iter().map(str::to_string)).collect())
+        })
+        .collect()
+}
+
+// The closest goal to "`numerator` times every `denominator` days", and
+// whether it's exact.
+fn goal_for(numerator: u32, denominator: u32) -> (GoalType, bool) {
+    match (numerator, denominator) {
+        (n, 1) => (GoalType::Daily, n == 1),
+        (n, 7) if n >= 7 => (GoalType::Daily, n == 7),
+        (n, 7) => (GoalType::Weekly(n), true),
+        (n, 30 | 31) => (GoalType::Monthly(n), true),
+        (n, d) => (GoalType::Weekly((n * 7).div_ceil(d.max(1)).clamp(1, 7)), false),
+    }
+}
+
+fn convert(loop_habit: LoopHabit, notes: &mut Vec<String>) -> Habit {
+    let name = loop_habit.name;
+    let (goal, exact) = goal_for(loop_habit.numerator, loop_habit.denominator);
+    if !exact {
+        notes.push(tn!("loop.frequency", loop_habit.numerator, name = name,
+            days = loop_habit.denominator, goal = goal.display()));
+    }
+    let mut habit = Habit::new(String::new(), name.clone(), goal, None);
+
+    // Numeric habits count a day with any amount as done.
+    let done = |value: f64| if loop_habit.numeric { value > 0.0 } else { value == YES_MANUAL };
+    let mut skipped = 0;
+    for &(date, value) in &loop_habit.entries {
+        if done(value) {
+            habit.mark_complete(date);
+        } else if value == SKIP && !loop_habit.numeric {
+            skipped += 1;
+            match habit.pauses.last_mut() {
+                Some(pause) if pause.to.succ_opt() == Some(date) => pause.to = date,
+                _ => habit.pauses.push(PausePeriod { from: date, to: date }),
+            }
+        }
+    }
+    if skipped > 0 {
+        notes.push(tn!("loop.skips", skipped, name = name));
+    }
+    if loop_habit.numeric {
+        let unit = if loop_habit.unit.is_empty() { "-".to_string() } else { loop_habit.unit };
+        notes.push(t!("loop.numeric", name = name, unit = unit));
+    }
+    if loop_habit.notes > 0 {
+        notes.push(tn!("loop.notes", loop_habit.notes, name = name));
+    }
+    if let Some(first) = loop_habit.entries.iter().map(|&(date, _)| date).min() {
+        habit.created_date = habit.created_date.min(first);
+    }
+    if let Some((hour, minute, days)) = loop_habit.reminder {
+        habit.reminder_time = Some(format!("{:02}:{:02}", hour, minute));
+        if days & EVERY_DAY != EVERY_DAY {
+            notes.push(t!("loop.reminder_days", name = name, time = habit.reminder_time.as_deref().unwrap_or("")));
+        }
+    }
+    if loop_habit.archived {
+        habit.archived_date = Some(Local::now().naive_local().date());
+        notes.push(t!("loop.archived", name = name));
+    }
+    habit
+}
@@ -1,9 +1,34 @@
-use chrono::{Local, NaiveDate, Datelike, Weekday};
-use colored::*;
+mod config;
+mod i18n;
+mod ics;
+mod import;
+mod loop_habits;
+mod output;
+mod parser;
+mod render;
+mod repl;
+mod report;
+mod svg;
+mod tag_expr;
+mod theme;
+mod tui;
+
+use chrono::{Local, NaiveDate, Datelike};
+use colored::Color;
+use rustyline::error::ReadlineError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use config::Config;
+use i18n::{t, tn};
+use import::{Mapping, Strategy};
+use output::OutputFormat;
+use report::ReportFormat;
+use render::{Paint, Role};
+use tag_expr::TagExpr;
+use theme::Theme;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 enum GoalType {
@@ -13,14 +38,16 @@ enum GoalType {
 }
 
 impl GoalType {
+    // Accepts what `label` writes too, such as "3/week".
     fn from_string(s: &str) -> Option<Self> {
+        let s = s.to_lowercase().replace('/', " ");
         let parts: Vec<&str> = s.split_whitespace().collect();
         match parts.as_slice() {
             ["daily"] => Some(GoalType::Daily),
-            [n, "per", "week"] | [n, "weekly"] => {
+            [n, "per", "week"] | [n, "weekly"] | [n, "week"] => {
                 n.parse::<u32>().ok().map(GoalType::Weekly)
             }
-            [n, "per", "month"] | [n, "monthly"] => {
+            [n, "per", "month"] | [n, "monthly"] | [n, "month"] => {
                 n.parse::<u32>().ok().map(GoalType::Monthly)
             }
             _ => None,
@@ -28,34 +55,140 @@ impl GoalType {
     }
 
     fn display(&self) -> String {
+        match self {
+            GoalType::Daily => t!("goal.daily"),
+            GoalType::Weekly(n) => t!("goal.weekly", count = n),
+            GoalType::Monthly(n) => t!("goal.monthly", count = n),
+        }
+    }
+
+    // The English form written to CSV exports, which don't follow the locale.
+    fn label(&self) -> String {
         match self {
             GoalType::Daily => "Daily".to_string(),
             GoalType::Weekly(n) => format!("{}/week", n),
             GoalType::Monthly(n) => format!("{}/month", n),
         }
     }
+
+    // Share of a 7-day weekly target contributed by a single day, in sevenths.
+    fn weekly_sevenths(&self) -> usize {
+        match self {
+            GoalType::Daily => 7,
+            GoalType::Weekly(n) => *n as usize,
+            GoalType::Monthly(_) => 7,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct GoalChange {
+    from: NaiveDate,
+    goal: GoalType,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct PausePeriod {
+    from: NaiveDate,
+    to: NaiveDate, // inclusive
+}
+
+impl PausePeriod {
+    fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.from && date <= self.to
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct Habit {
+    #[serde(default)]
+    id: String, // stable slug, assigned once and kept across renames
     name: String,
     created_date: NaiveDate,
     completions: Vec<NaiveDate>,
     goal: GoalType,
     category: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
     reminder_time: Option<String>, // HH:MM format
+    #[serde(default)]
+    pauses: Vec<PausePeriod>,
+    #[serde(default)]
+    archived_date: Option<NaiveDate>,
+    #[serde(default)]
+    goal_history: Vec<GoalChange>, // empty until the goal is first edited
+}
+
+enum HabitEdit {
+    Name(String),
+    Goal(GoalType, NaiveDate),
+    Category(Option<String>),
+    Reminder(Option<String>),
+    Created(NaiveDate),
 }
 
 impl Habit {
-    fn new(name: String, goal: GoalType, category: Option<String>) -> Self {
+    fn new(id: String, name: String, goal: GoalType, category: Option<String>) -> Self {
         Habit {
+            id,
             name,
             created_date: Local::now().naive_local().date(),
             completions: Vec::new(),
             goal,
             category,
+            tags: Vec::new(),
             reminder_time: None,
+            pauses: Vec::new(),
+            archived_date: None,
+            goal_history: Vec::new(),
+        }
+    }
+
+    fn goal_on(&self, date: NaiveDate) -> &GoalType {
+        self.goal_history
+            .iter()
+            .rev()
+            .find(|c| c.from <= date)
+            .or(self.goal_history.first())
+            .map_or(&self.goal, |c| &c.goal)
+    }
+
+    fn set_goal(&mut self, goal: GoalType, from: NaiveDate) {
+        self.set_goal_as_of(goal, from, Local::now().naive_local().date());
+    }
+
+    fn set_goal_as_of(&mut self, goal: GoalType, from: NaiveDate, today: NaiveDate) {
+        if self.goal_history.is_empty() {
+            self.goal_history.push(GoalChange {
+                from: self.created_date,
+                goal: self.goal.clone(),
+            });
         }
+        // A change replaces anything scheduled on or after its start date.
+        self.goal_history.retain(|c| c.from < from);
+        self.goal_history.push(GoalChange { from, goal: goal.clone() });
+        self.goal = self.goal_on(today).clone();
+    }
+
+    fn is_archived(&self) -> bool {
+        self.archived_date.is_some()
+    }
+
+    fn is_paused(&self, date: NaiveDate) -> bool {
+        self.pauses.iter().any(|p| p.contains(date))
+    }
+
+    fn active_pause(&self) -> Option<&PausePeriod> {
+        let today = Local::now().naive_local().date();
+        self.pauses.iter().find(|p| p.contains(today))
+    }
+
+    // True when every day strictly between `a` and `b` falls inside a pause.
+    fn only_paused_between(&self, a: NaiveDate, b: NaiveDate) -> bool {
+        a.iter_days()
+            .skip(1)
+            .take_while(|d| *d < b)
+            .all(|d| self.is_paused(d))
     }
 
     fn mark_complete(&mut self, date: NaiveDate) -> bool {
@@ -68,37 +201,32 @@ impl Habit {
         }
     }
 
+    fn unmark_complete(&mut self, date: NaiveDate) -> bool {
+        let before = self.completions.len();
+        self.completions.retain(|&d| d != date);
+        self.completions.len() != before
+    }
+
     fn current_streak(&self) -> u32 {
-        let today = Local::now().naive_local().date();
+        self.current_streak_as_of(Local::now().naive_local().date())
+    }
+
+    fn current_streak_as_of(&self, today: NaiveDate) -> u32 {
         let mut streak = 0;
         let mut check_date = today;
 
+        // Paused days neither extend nor break a streak, and today gets a
+        // grace period until it is marked done.
         loop {
             if self.completions.contains(&check_date) {
                 streak += 1;
-                check_date = match check_date.pred_opt() {
-                    Some(d) => d,
-                    None => break,
-                };
-            } else {
-                if check_date == today && streak == 0 {
-                    check_date = match check_date.pred_opt() {
-                        Some(d) => d,
-                        None => break,
-                    };
-                    if self.completions.contains(&check_date) {
-                        streak += 1;
-                        check_date = match check_date.pred_opt() {
-                            Some(d) => d,
-                            None => break,
-                        };
-                    } else {
-                        break;
-                    }
-                } else {
-                    break;
-                }
+            } else if !(self.is_paused(check_date) || (check_date == today && streak == 0)) {
+                break;
             }
+            check_date = match check_date.pred_opt() {
+                Some(d) => d,
+                None => break,
+            };
         }
         streak
     }
@@ -112,11 +240,7 @@ impl Habit {
         let mut current_streak = 1;
 
         for i in 1..self.completions.len() {
-            let diff = self.completions[i]
-                .signed_duration_since(self.completions[i - 1])
-                .num_days();
-
-            if diff == 1 {
+            if self.only_paused_between(self.completions[i - 1], self.completions[i]) {
                 current_streak += 1;
                 max_streak = max_streak.max(current_streak);
             } else {
@@ -132,7 +256,7 @@ impl Habit {
         let week_ago = today - chrono::Duration::days(7);
         self.completions
             .iter()
-            .filter(|&&d| d > week_ago && d <= today)
+            .filter(|&&d| d > week_ago && d <= today && !self.is_paused(d))
             .count()
     }
 
@@ -141,42 +265,87 @@ impl Habit {
         let month_ago = today - chrono::Duration::days(30);
         self.completions
             .iter()
-            .filter(|&&d| d > month_ago && d <= today)
+            .filter(|&&d| d > month_ago && d <= today && !self.is_paused(d))
             .count()
     }
 
+    // How `date` looks in the SVG charts.
+    fn day_state(&self, date: NaiveDate, today: NaiveDate) -> svg::Day {
+        if self.completions.contains(&date) {
+            svg::Day::Done
+        } else if date > today || date < self.created_date {
+            svg::Day::Open
+        } else if self.is_paused(date) {
+            svg::Day::Paused
+        } else {
+            svg::Day::Missed
+        }
+    }
+
     fn is_done_today(&self) -> bool {
         let today = Local::now().naive_local().date();
         self.completions.contains(&today)
     }
 
+    // Progress through the calendar week, which begins on the configured
+    // `week_start`.
     fn goal_progress_this_week(&self) -> (usize, usize) {
-        let count = self.weekly_count();
-        let target = match self.goal {
-            GoalType::Daily => 7,
-            GoalType::Weekly(n) => n as usize,
-            GoalType::Monthly(_) => 7,
+        self.week_progress_as_of(config::current().week_start, Local::now().naive_local().date())
+    }
+
+    fn week_progress_as_of(&self, week_start: config::WeekStart, today: NaiveDate) -> (usize, usize) {
+        let first = week_start.week_of(today);
+        self.progress_as_of(first, first + chrono::Duration::days(6), today)
+    }
+
+    // (completions so far, target) from `first` to `last`. Each active day
+    // contributes its share of the goal in force on that day, so paused days
+    // and goal changes both shrink or grow the target. Over a whole calendar
+    // month a monthly goal counts in full; in shorter periods it's daily.
+    fn progress_between(&self, first: NaiveDate, last: NaiveDate) -> (usize, usize) {
+        self.progress_as_of(first, last, Local::now().naive_local().date())
+    }
+
+    fn progress_as_of(&self, first: NaiveDate, last: NaiveDate, today: NaiveDate) -> (usize, usize) {
+        let days: Vec<NaiveDate> = first.iter_days().take_while(|d| *d <= last).collect();
+        let count = days.iter()
+            .filter(|&&d| d <= today && !self.is_paused(d) && self.completions.contains(&d))
+            .count();
+        let whole_month = first.day() == 1 && (first.year(), first.month()) == (last.year(), last.month())
+            && last.succ_opt().is_some_and(|d| d.day() == 1);
+        // Shares are in 1/(7 * scale) of a completion.
+        let scale = if whole_month { days.len() } else { 1 };
+        let share = |goal: &GoalType| match goal {
+            GoalType::Monthly(n) if whole_month => 7 * *n as usize,
+            goal => goal.weekly_sevenths() * scale,
         };
+        let target = days.iter()
+            .filter(|&&d| !self.is_paused(d))
+            .map(|&d| share(self.goal_on(d)))
+            .sum::<usize>()
+            .div_ceil(7 * scale);
         (count, target)
     }
 
     fn goal_status(&self) -> String {
+        if self.active_pause().is_some() {
+            return t!("status.paused").paint(Role::Paused);
+        }
+
         let (current, target) = self.goal_progress_this_week();
-        let percentage = (current as f32 / target as f32 * 100.0) as u32;
+        let percentage = (current as f32 / target.max(1) as f32 * 100.0) as u32;
 
         if current >= target {
-            "✓ Goal Met!".green().to_string()
-        } else if percentage >= 70 {
-            format!("⚠ {}/{}", current, target).yellow().to_string()
-        } else {
-            format!("○ {}/{}", current, target).red().to_string()
+            return t!("status.goal_met").paint(Role::Success);
         }
+        let role = render::progress_role(percentage);
+        format!("{} {}/{}", render::status_mark(role), current, target).paint(role)
     }
 
-    fn progress_bar(&self, total: usize) -> String {
-        let completed = self.weekly_count();
-        let filled = (completed * 10) / total.max(1);
-        let empty = 10 - filled;
+    fn progress_bar(completed: usize, total: usize) -> String {
+        let width = config::current().progress_bar_width;
+        let filled = ((completed * width) / total.max(1)).min(width);
+        let empty = width - filled;
 
         let bar = format!(
             "{}{}",
@@ -184,48 +353,58 @@ impl Habit {
             "░".repeat(empty)
         );
 
-        if completed >= total {
-            bar.green().to_string()
-        } else if completed >= (total * 7 / 10) {
-            bar.yellow().to_string()
-        } else {
-            bar.red().to_string()
-        }
+        bar.paint(render::progress_role((completed * 100 / total.max(1)) as u32))
     }
 
     fn get_calendar_month(&self, year: i32, month: u32) -> String {
         use chrono::NaiveDate;
 
         let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-        let days_in_month = if month == 12 {
-            NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap()
-        } else {
-            NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap()
-        }.signed_duration_since(first_day).num_days();
+        let days_in_month = days_in_month(year, month);
 
         let mut output = String::new();
         output.push_str(&format!("\n  {} {}\n",
-            first_day.format("%B"),
+            i18n::month_name(month),
             year
         ));
-        output.push_str("  Mo Tu We Th Fr Sa Su\n  ");
+        let week_start = config::current().week_start;
+        let header: Vec<String> = week_start.days().into_iter().map(i18n::weekday_initials).collect();
+        output.push_str(&format!("  {}\n  ", header.join(" ")));
 
-        let start_weekday = first_day.weekday().num_days_from_monday();
-        for _ in 0..start_weekday {
+        for _ in 0..week_start.offset(first_day.weekday()) {
             output.push_str("   ");
         }
 
+        let today = Local::now().naive_local().date();
         for day in 1..=days_in_month {
-            let date = NaiveDate::from_ymd_opt(year, month, day as u32).unwrap();
-            let day_str = if self.completions.contains(&date) {
-                format!("{:2}", day).green().bold().to_string()
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            let done = self.completions.contains(&date);
+            let number = format!("{:2}", day);
+            let number = if done {
+                number.paint(Role::Done)
+            } else if date == today {
+                number.paint(Role::Today)
+            } else if date == self.created_date {
+                number.paint(Role::Accent)
+            } else if date > today || date < self.created_date {
+                number.paint(Role::Muted)
             } else {
-                format!("{:2}", day)
+                number
             };
+            // Today and the creation date get a marker after the number.
+            // Without colors a completion needs one too, and it comes first.
+            let marker = if done && !render::colors_enabled() {
+                "*"
+            } else if date == today {
+                "<"
+            } else if date == self.created_date {
+                "+"
+            } else {
+                " "
+            };
+            output.push_str(&format!("{}{}", number, marker));
 
-            output.push_str(&format!("{} ", day_str));
-
-            if date.weekday() == Weekday::Sun {
+            if date.weekday() == week_start.weekday().pred() {
                 output.push_str("\n  ");
             }
         }
@@ -234,208 +413,951 @@ impl Habit {
     }
 }
 
+// Shading for heatmap days, from none of the habits done to all of them.
+const HEAT_LEVELS: [(&str, &str); 5] = [("·", "."), ("░", "-"), ("▒", "+"), ("▓", "*"), ("█", "#")];
+
+// A contribution graph from `first` to `last` with a column per week and a
+// row per weekday. Each day is shaded by the share of `habits` that existed
+// then and were completed, so a single habit's days are empty or full.
+// How many of the habits that existed on `date` were done that day, as an
+// index into HEAT_LEVELS, or None if none existed yet. Completions from before
+// a habit was created don't count, as the habit isn't among those that existed.
+fn heat_level(habits: &[&Habit], date: NaiveDate) -> Option<usize> {
+    let existing: Vec<&Habit> = habits.iter().copied().filter(|h| h.created_date <= date).collect();
+    if existing.is_empty() {
+        return None;
+    }
+    let done = existing.iter().filter(|h| h.completions.contains(&date)).count();
+    Some((done * 4).div_ceil(existing.len()))
+}
+
+fn get_heatmap(habits: &[&Habit], first: NaiveDate, last: NaiveDate) -> String {
+    let week_start = config::current().week_start;
+    let today = Local::now().naive_local().date();
+    let start = week_start.week_of(first);
+    let weeks = (last - start).num_days() / 7 + 1;
+    let date_at = |week: i64, row: i64| start + chrono::Duration::days(week * 7 + row);
+
+    // Month names go above the week holding the 1st, if there's room.
+    let mut labels = String::new();
+    let mut width = 0;
+    for week in 0..weeks {
+        let Some(date) = (0..7).map(|row| date_at(week, row)).find(|d| d.day() == 1 && *d >= first && *d <= last) else {
+            continue;
+        };
+        if week as usize >= width + usize::from(width > 0) {
+            labels.push_str(&" ".repeat(week as usize - width));
+            let name = i18n::format_date(date, "%b");
+            width = week as usize + name.chars().count();
+            labels.push_str(&name);
+        }
+    }
+
+    let mut output = format!("\n     {}\n", labels);
+    for (row, day) in week_start.days().into_iter().enumerate() {
+        output.push_str(&format!("  {} ", i18n::weekday_initials(day)));
+        for week in 0..weeks {
+            let date = date_at(week, row as i64);
+            let in_range = date >= first && date <= last && date <= today;
+            let Some(level) = heat_level(habits, date).filter(|_| in_range) else {
+                output.push(' ');
+                continue;
+            };
+            let (fancy, ascii) = HEAT_LEVELS[level];
+            let role = if level == 0 { Role::Muted } else { Role::Done };
+            output.push_str(&render::glyph(fancy, ascii).paint(role));
+        }
+        output.push('\n');
+    }
+    let scale: Vec<String> = HEAT_LEVELS.iter().enumerate()
+        .map(|(level, &(fancy, ascii))| render::glyph(fancy, ascii).paint(if level == 0 { Role::Muted } else { Role::Done }))
+        .collect();
+    output.push_str(&format!("\n     {} {} {}\n", t!("heatmap.less").paint(Role::Muted), scale.join(" "), t!("heatmap.more").paint(Role::Muted)));
+    output
+}
+
+// A habit's cell in the grid for one day: done, missed, paused, still open
+// today, or blank when the habit didn't exist yet or the day is to come.
+fn grid_mark(habit: &Habit, date: NaiveDate, today: NaiveDate) -> String {
+    if habit.completions.contains(&date) {
+        render::glyph("✓", "x").paint(Role::Done)
+    } else if date > today || date < habit.created_date {
+        " ".to_string()
+    } else if habit.is_paused(date) {
+        render::glyph("–", "p").paint(Role::Muted)
+    } else if date == today {
+        render::glyph("·", ".").paint(Role::Muted)
+    } else {
+        render::glyph("✗", "-").paint(Role::Error)
+    }
+}
+
+// Habits as rows and the days from `first` to `last` as columns, with each
+// row's completions out of the days it was due.
+fn get_habit_grid(habits: &[&Habit], first: NaiveDate, last: NaiveDate) -> String {
+    let today = Local::now().naive_local().date();
+    let days: Vec<NaiveDate> = first.iter_days().take_while(|d| *d <= last).collect();
+    let width = habits.iter().map(|h| h.name.chars().count()).max().unwrap_or(0).clamp(5, 24);
+
+    let initials: String = days.iter().map(|d| format!(" {}", i18n::weekday_initials(d.weekday()))).collect();
+    let numbers: String = days.iter()
+        .map(|&d| {
+            let number = format!(" {:>2}", d.day());
+            if d == today { number.paint(Role::Today) } else { number.paint(Role::Muted) }
+        })
+        .collect();
+    let mut output = format!("\n  {:width$} {}\n  {:width$} {}\n", "", initials, "", numbers);
+
+    for habit in habits {
+        let name = if habit.name.chars().count() > width {
+            format!("{}…", habit.name.chars().take(width - 1).collect::<String>())
+        } else {
+            habit.name.clone()
+        };
+        let padding = " ".repeat(width - name.chars().count());
+        let cells: String = days.iter().map(|&d| format!("  {}", grid_mark(habit, d, today))).collect();
+        let due = days.iter()
+            .filter(|&&d| d >= habit.created_date && d <= today && !habit.is_paused(d))
+            .count();
+        let done = days.iter().filter(|d| habit.completions.contains(d)).count();
+        output.push_str(&format!("  {}{} {}  {}\n", name.paint(Role::Name), padding, cells,
+            format!("{}/{}", done, due).paint(Role::Muted)));
+    }
+    output
+}
+
+// Display settings and goals for a category. Habits refer to categories by
+// name; a category only needs an entry here once it has been customized.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct Category {
+    color: Option<String>,
+    icon: Option<String>,
+    goal_percent: Option<u32>, // share of the category's habits meeting their weekly goal
+}
+
+enum CategoryChange {
+    Color(Option<String>),
+    Icon(Option<String>),
+    Goal(Option<u32>),
+}
+
+// What `export` writes: a row per habit with its stats, a row per
+// completion, or a table of dates against habits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportMode {
+    Summary,
+    Completions,
+    Matrix,
+}
+
+impl ExportMode {
+    fn from_flag(flag: Option<&str>) -> Result<Self, String> {
+        match flag.map(str::to_lowercase).as_deref() {
+            None | Some("summary") => Ok(ExportMode::Summary),
+            Some("completions") => Ok(ExportMode::Completions),
+            Some("matrix") => Ok(ExportMode::Matrix),
+            Some(other) => Err(t!("export.unknown_mode", mode = other)),
+        }
+    }
+}
+
+// The charts `chart` draws into its SVG file; all of them without --type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChartKind {
+    Rate,
+    Weekly,
+    Heatmap,
+}
+
+impl ChartKind {
+    fn from_flag(flag: Option<&str>) -> Result<Vec<Self>, String> {
+        match flag.map(str::to_lowercase).as_deref() {
+            None | Some("all") => Ok(vec![ChartKind::Rate, ChartKind::Weekly, ChartKind::Heatmap]),
+            Some("rate") => Ok(vec![ChartKind::Rate]),
+            Some("weekly") => Ok(vec![ChartKind::Weekly]),
+            Some("heatmap") => Ok(vec![ChartKind::Heatmap]),
+            Some(other) => Err(t!("chart.unknown_type", kind = other)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct HabitTracker {
-    habits: HashMap<String, Habit>,
+    habits: HashMap<String, Habit>, // keyed by Habit::id
+    #[serde(default)]
+    categories: BTreeMap<String, Category>,
+}
+
+// The month `delta` months after the given one, as long as it and the month
+// after it hold dates chrono can represent.
+fn shift_month(year: i32, month: u32, delta: i32) -> Option<(i32, u32)> {
+    let index = year.checked_mul(12)?.checked_add(month as i32 - 1)?.checked_add(delta)?;
+    let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+    let next = index.checked_add(1)?;
+    NaiveDate::from_ymd_opt(year, month, 1)?;
+    NaiveDate::from_ymd_opt(next.div_euclid(12), next.rem_euclid(12) as u32 + 1, 1)?;
+    Some((year, month))
+}
+
+// A month given as "2025-03", or relative to `today` as "-2" or "+1".
+fn parse_month(arg: &str, today: NaiveDate) -> Option<(i32, u32)> {
+    if let Some(delta) = arg.strip_prefix(['-', '+']).filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())) {
+        let delta: i32 = delta.parse().ok()?;
+        let delta = if arg.starts_with('-') { -delta } else { delta };
+        return shift_month(today.year(), today.month(), delta);
+    }
+    let date = NaiveDate::parse_from_str(&format!("{}-01", arg), "%Y-%m-%d").ok()?;
+    shift_month(date.year(), date.month(), 0)
+}
+
+// The first and last day of a week or month given the way `grid` and
+// `report` take them: nothing for the current one, "-1" or "+2" to move from
+// it, a date for the week holding it, or "2025-03" for a month.
+fn parse_period(monthly: bool, args: &[&str]) -> Option<(NaiveDate, NaiveDate)> {
+    let today = Local::now().naive_local().date();
+    if monthly {
+        let (year, month) = match args {
+            [] => (today.year(), today.month()),
+            [arg] => parse_month(arg, today)?,
+            _ => return None,
+        };
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let (next_year, next_month) = shift_month(year, month, 1)?;
+        let last = NaiveDate::from_ymd_opt(next_year, next_month, 1)?.checked_sub_days(chrono::Days::new(1))?;
+        return Some((first, last));
+    }
+    let day = match args {
+        [] => today,
+        [arg] if arg.starts_with(['-', '+']) => today.checked_add_signed(chrono::TimeDelta::try_weeks(arg.parse().ok()?)?)?,
+        [arg] => NaiveDate::parse_from_str(arg, "%Y-%m-%d").ok()?,
+        _ => return None,
+    };
+    // The week around the day has to fit in the range of dates too.
+    day.checked_sub_days(chrono::Days::new(6))?;
+    let first = config::current().week_start.week_of(day);
+    Some((first, first.checked_add_days(chrono::Days::new(6))?))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let next_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap()
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap()
+    };
+    next_month.signed_duration_since(first_day).num_days() as u32
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            current.push(substitution.min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.trim_end_matches('-').chars().take(20).collect();
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "habit".to_string() } else { slug.to_string() }
 }
 
 impl HabitTracker {
     fn new() -> Self {
         HabitTracker {
             habits: HashMap::new(),
+            categories: BTreeMap::new(),
         }
     }
 
     fn load() -> Self {
-        match fs::read_to_string("habits.json") {
+        let mut tracker = match fs::read_to_string(config::current().data_file) {
             Ok(data) => serde_json::from_str(&data).unwrap_or_else(|_| HabitTracker::new()),
             Err(_) => HabitTracker::new(),
+        };
+        tracker.assign_ids();
+        tracker
+    }
+
+    // Older data files keyed habits by name and had no IDs; re-key everything
+    // by ID, generating one for any habit that is missing it.
+    fn assign_ids(&mut self) {
+        let (with_id, mut without_id): (Vec<Habit>, Vec<Habit>) = self.habits
+            .drain()
+            .map(|(_, h)| h)
+            .partition(|h| !h.id.is_empty());
+        for habit in with_id {
+            self.habits.insert(habit.id.clone(), habit);
+        }
+        without_id.sort_by(|a, b| a.name.cmp(&b.name));
+        for mut habit in without_id {
+            habit.id = self.unique_id(&habit.name);
+            self.habits.insert(habit.id.clone(), habit);
+        }
+    }
+
+    fn unique_id(&self, name: &str) -> String {
+        let base = slugify(name);
+        let mut id = base.clone();
+        let mut n = 2;
+        while self.habits.contains_key(&id) {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+        id
+    }
+
+    fn find_exact(&self, query: &str) -> Option<&Habit> {
+        let id = query.strip_prefix('#').unwrap_or(query);
+        self.habits.get(id).or_else(|| self.habits.values().find(|h| h.name == query))
+    }
+
+    // Finds a habit by ID or name, trying progressively looser matches:
+    // exact, case-insensitive, prefix, then substring. The first tier with
+    // any match wins; on failure returns that tier's candidate IDs (empty if
+    // nothing matched at all).
+    fn resolve(&self, query: &str) -> Result<String, Vec<String>> {
+        if let Some(habit) = self.find_exact(query) {
+            return Ok(habit.id.clone());
+        }
+        let query = query.to_lowercase();
+        let id_query = query.strip_prefix('#').unwrap_or(&query);
+        let tiers: [&dyn Fn(&Habit) -> bool; 3] = [
+            &|h| h.id == id_query || h.name.to_lowercase() == query,
+            &|h| h.id.starts_with(id_query) || h.name.to_lowercase().starts_with(&query),
+            &|h| h.name.to_lowercase().contains(&query),
+        ];
+
+        for matches_tier in tiers {
+            let mut matches: Vec<String> = self.habits.values()
+                .filter(|h| matches_tier(h))
+                .map(|h| h.id.clone())
+                .collect();
+            matches.sort();
+            match matches.len() {
+                0 => continue,
+                1 => return Ok(matches.remove(0)),
+                _ => return Err(matches),
+            }
+        }
+        Err(Vec::new())
+    }
+
+    // Names within a small edit distance of `query`, closest first.
+    fn suggestions(&self, query: &str) -> Vec<&str> {
+        let query = query.to_lowercase();
+        let max_distance = (query.chars().count() / 3).max(2);
+        let mut close: Vec<(usize, &str)> = self.habits.values()
+            .map(|h| (levenshtein(&query, &h.name.to_lowercase()), h.name.as_str()))
+            .filter(|(d, _)| *d <= max_distance)
+            .collect();
+        close.sort();
+        close.into_iter().take(3).map(|(_, name)| name).collect()
+    }
+
+    fn resolve_or_report(&self, query: &str) -> Option<String> {
+        match self.resolve(query) {
+            Ok(id) => Some(id),
+            Err(ids) if ids.is_empty() => {
+                fail(t!("habit.not_found", name = query));
+                let suggestions = self.suggestions(query);
+                if !suggestions.is_empty() {
+                    let quoted: Vec<String> = suggestions.iter().map(|n| format!("'{}'", n)).collect();
+                    eprintln!("{}", t!("habit.did_you_mean", names = quoted.join(&t!("common.or"))).paint(Role::Muted));
+                }
+                None
+            }
+            Err(ids) => self.choose_habit(query, &ids),
+        }
+    }
+
+    fn choose_habit(&self, query: &str, ids: &[String]) -> Option<String> {
+        println!("{}", t!("habit.ambiguous", query = query).paint(Role::Warning));
+        for (i, id) in ids.iter().enumerate() {
+            println!("  {}) {} {}", i + 1, self.habits[id].name.paint(Role::Value), format!("#{}", id).paint(Role::Muted));
+        }
+        print!("{}", t!("habit.choose"));
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).ok()?;
+        match input.trim().parse::<usize>() {
+            Ok(n) if (1..=ids.len()).contains(&n) => Some(ids[n - 1].clone()),
+            _ => {
+                println!("{}", t!("common.cancelled").paint(Role::Muted));
+                None
+            }
         }
     }
 
+    fn lookup(&self, query: &str) -> Option<&Habit> {
+        let id = self.resolve_or_report(query)?;
+        self.habits.get(&id)
+    }
+
+    fn lookup_mut(&mut self, query: &str) -> Option<&mut Habit> {
+        let id = self.resolve_or_report(query)?;
+        self.habits.get_mut(&id)
+    }
+
+    // Splits leading habit-name words off `args`, preferring the longest
+    // run that names a habit. Returns the name and how many words it used.
+    fn split_habit_name(&self, args: &[&str]) -> (String, usize) {
+        let candidates = || (1..args.len()).rev().map(|k| (args[..k].join(" "), k));
+        candidates()
+            .find(|(name, _)| self.find_exact(name).is_some())
+            .or_else(|| candidates().find(|(name, _)| self.resolve(name).is_ok()))
+            .unwrap_or_else(|| (args[0].to_string(), 1))
+    }
+
     fn save(&self) -> io::Result<()> {
-        let json = serde_json::to_string_pretty(&self)?;
-        fs::write("habits.json", json)?;
+        let config = config::current();
+        self.write_to(&config.data_file)?;
+        // Keeps a calendar subscribed to the file up to date. The habits are
+        // saved either way, so a calendar that can't be written only warns.
+        if config.calendar_file.is_empty() {
+            return Ok(());
+        }
+        if let Err(e) = ics::write(self, &config.calendar_file) {
+            println!("{}", t!("ics.write_failed", file = config.calendar_file, error = e).paint(Role::Warning));
+        }
         Ok(())
     }
 
+    fn write_to(&self, path: &str) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self)?;
+        fs::write(path, json)
+    }
+
+    // Saves after a change, reporting a failure rather than stopping.
+    fn save_or_report(&self) {
+        if let Err(e) = self.save() {
+            let file = config::current().data_file;
+            fail(format!("❌ {}", t!("config.write_failed", file = file, error = e)));
+        }
+    }
+
     fn add_habit(&mut self, name: String, goal: GoalType, category: Option<String>) {
-        if self.habits.contains_key(&name) {
-            println!("{}", format!("❌ Habit '{}' already exists!", name).red());
+        if self.habits.values().any(|h| h.name == name) {
+            fail(t!("habit.exists", name = name));
         } else {
-            self.habits.insert(name.clone(), Habit::new(name.clone(), goal, category));
-            println!("{}", format!("✅ Added habit '{}'", name).green());
+            let id = self.unique_id(&name);
+            self.habits.insert(id.clone(), Habit::new(id.clone(), name.clone(), goal, category));
+            println!("{}", t!("habit.added", name = name, id = id).paint(Role::Success));
         }
     }
 
     fn remove_habit(&mut self, name: &str) {
-        if self.habits.remove(name).is_some() {
-            println!("{}", format!("✅ Removed habit '{}'", name).green());
-        } else {
-            println!("{}", format!("❌ Habit '{}' not found!", name).red());
+        if let Some(id) = self.resolve_or_report(name) {
+            let habit = self.habits.remove(&id).unwrap();
+            println!("{}", t!("habit.removed", name = habit.name).paint(Role::Success));
         }
     }
 
     fn mark_done(&mut self, name: &str) {
-        if let Some(habit) = self.habits.get_mut(name) {
+        if let Some(habit) = self.lookup_mut(name) {
+            if habit.is_archived() {
+                fail(t!("habit.done_archived", name = habit.name, id = habit.id));
+                return;
+            }
             let today = Local::now().naive_local().date();
             if habit.mark_complete(today) {
-                println!("{}", format!("✅ Marked '{}' as done for today! 🎉", name).green().bold());
+                println!("{}", t!("habit.marked_done", name = habit.name).paint(Role::Celebrate));
 
                 let (current, target) = habit.goal_progress_this_week();
                 if current == target {
-                    println!("{}", "🎯 Weekly goal achieved! Great job!".yellow().bold());
+                    println!("{}", t!("habit.weekly_goal_met").paint(Role::Celebrate));
                 }
             } else {
-                println!("{}", format!("ℹ️  '{}' was already marked done today.", name).cyan());
+                println!("{}", t!("habit.already_done", name = habit.name).paint(Role::Info));
             }
-        } else {
-            println!("{}", format!("❌ Habit '{}' not found!", name).red());
         }
     }
 
     fn set_category(&mut self, name: &str, category: String) {
-        if let Some(habit) = self.habits.get_mut(name) {
+        if let Some(habit) = self.lookup_mut(name) {
             habit.category = Some(category.clone());
-            println!("{}", format!("✅ Set category '{}' for '{}'", category, name).green());
-        } else {
-            println!("{}", format!("❌ Habit '{}' not found!", name).red());
+            println!("{}", t!("category.set", category = category, name = habit.name).paint(Role::Success));
+        }
+    }
+
+    fn add_tags(&mut self, name: &str, tags: &[&str]) {
+        if let Some(habit) = self.lookup_mut(name) {
+            for tag in tags {
+                if !habit.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    habit.tags.push(tag.to_string());
+                }
+            }
+            habit.tags.sort_by_key(|t| t.to_lowercase());
+            println!("{}", t!("tag.tags_for", name = habit.name, tags = habit.tags.join(", ")).paint(Role::Success));
+        }
+    }
+
+    fn remove_tags(&mut self, name: &str, tags: &[&str]) {
+        if let Some(habit) = self.lookup_mut(name) {
+            habit.tags.retain(|t| !tags.iter().any(|r| t.eq_ignore_ascii_case(r)));
+            if habit.tags.is_empty() {
+                println!("{}", t!("tag.none_left", name = habit.name).paint(Role::Success));
+            } else {
+                println!("{}", t!("tag.tags_for", name = habit.name, tags = habit.tags.join(", ")).paint(Role::Success));
+            }
         }
     }
 
     fn set_reminder(&mut self, name: &str, time: String) {
-        if let Some(habit) = self.habits.get_mut(name) {
+        if let Some(habit) = self.lookup_mut(name) {
             habit.reminder_time = Some(time.clone());
-            println!("{}", format!("✅ Set reminder for '{}' at {}", name, time).green());
+            let name = habit.name.clone();
+            println!("{}", t!("reminder.set", name = name, time = time).paint(Role::Success));
             #[cfg(not(target_os = "windows"))]
             self.show_notification(
-                "Reminder Set",
-                &format!("You'll be reminded about '{}' at {}", name, time),
+                &t!("reminder.set_title"),
+                &t!("reminder.set_body", name = name, time = time),
             );
-        } else {
-            println!("{}", format!("❌ Habit '{}' not found!", name).red());
         }
     }
 
-    fn show_habits(&self, filter_category: Option<&str>) {
-        let filtered_habits: Vec<_> = self.habits.values()
+    fn pause_habit(&mut self, target: &str, from: NaiveDate, to: NaiveDate) {
+        let period = PausePeriod { from, to };
+        if target.eq_ignore_ascii_case("all") && self.find_exact(target).is_none() {
+            for habit in self.habits.values_mut() {
+                habit.pauses.push(period.clone());
+            }
+            println!("{}", t!("pause.all", from = config::format_date(from), to = config::format_date(to)).paint(Role::Success));
+        } else if let Some(habit) = self.lookup_mut(target) {
+            habit.pauses.push(period);
+            println!("{}", t!("pause.one", name = habit.name, from = config::format_date(from), to = config::format_date(to)).paint(Role::Success));
+        }
+    }
+
+    fn resume_habit(&mut self, target: &str) {
+        let today = Local::now().naive_local().date();
+        let yesterday = today.pred_opt().unwrap_or(today);
+        // Drop pauses that haven't started yet and cut the current one short,
+        // keeping past pauses so historical streaks stay the same.
+        let resume = |habit: &mut Habit| {
+            habit.pauses.retain(|p| p.from < today);
+            for p in habit.pauses.iter_mut().filter(|p| p.to >= today) {
+                p.to = yesterday;
+            }
+        };
+
+        if target.eq_ignore_ascii_case("all") && self.find_exact(target).is_none() {
+            self.habits.values_mut().for_each(resume);
+            println!("{}", t!("pause.resumed_all").paint(Role::Success));
+        } else if let Some(habit) = self.lookup_mut(target) {
+            resume(habit);
+            println!("{}", t!("pause.resumed", name = habit.name).paint(Role::Success));
+        }
+    }
+
+    fn edit_habit(&mut self, query: &str, edit: HabitEdit) {
+        let Some(id) = self.resolve_or_report(query) else {
+            return;
+        };
+        if let HabitEdit::Name(ref new_name) = edit
+            && self.habits.values().any(|h| h.name == *new_name && h.id != id)
+        {
+            fail(t!("habit.exists", name = new_name));
+            return;
+        }
+        let habit = self.habits.get_mut(&id).unwrap();
+        let name = habit.name.clone();
+
+        match edit {
+            HabitEdit::Name(new_name) => {
+                habit.name = new_name.clone();
+                println!("{}", t!("edit.renamed", old = name, new = new_name, id = id).paint(Role::Success));
+            }
+            HabitEdit::Goal(goal, from) => {
+                habit.set_goal(goal.clone(), from);
+                println!("{}", t!("edit.goal", name = name, goal = goal.display(), date = config::format_date(from)).paint(Role::Success));
+            }
+            HabitEdit::Category(category) => {
+                match &category {
+                    Some(cat) => println!("{}", t!("category.set", category = cat, name = name).paint(Role::Success)),
+                    None => println!("{}", t!("category.cleared", name = name).paint(Role::Success)),
+                }
+                habit.category = category;
+            }
+            HabitEdit::Reminder(time) => {
+                match &time {
+                    Some(time) => println!("{}", t!("reminder.set", name = name, time = time).paint(Role::Success)),
+                    None => println!("{}", t!("reminder.cleared", name = name).paint(Role::Success)),
+                }
+                habit.reminder_time = time;
+            }
+            HabitEdit::Created(date) => {
+                if let Some(first) = habit.completions.first().filter(|&&d| d < date) {
+                    fail(t!("edit.completed_before", name = name, date = config::format_date(*first)));
+                    return;
+                }
+                habit.created_date = date;
+                if let Some(first) = habit.goal_history.first_mut() {
+                    first.from = first.from.min(date);
+                }
+                println!("{}", t!("edit.created", name = name, date = config::format_date(date)).paint(Role::Success));
+            }
+        }
+    }
+
+    fn archive_habit(&mut self, name: &str) {
+        if let Some(habit) = self.lookup_mut(name) {
+            if habit.is_archived() {
+                println!("{}", t!("archive.already", name = habit.name).paint(Role::Info));
+            } else {
+                habit.archived_date = Some(Local::now().naive_local().date());
+                println!("{}", t!("archive.archived", name = habit.name).paint(Role::Success));
+            }
+        }
+    }
+
+    fn unarchive_habit(&mut self, name: &str) {
+        if let Some(habit) = self.lookup_mut(name) {
+            if habit.archived_date.take().is_some() {
+                println!("{}", t!("archive.restored", name = habit.name).paint(Role::Success));
+            } else {
+                println!("{}", t!("archive.not_archived", name = habit.name).paint(Role::Info));
+            }
+        }
+    }
+
+    fn show_archived(&self, format: OutputFormat) {
+        let mut archived: Vec<_> = self.habits.values().filter(|h| h.is_archived()).collect();
+        archived.sort_by(|a, b| a.name.cmp(&b.name));
+        if format != OutputFormat::Text {
+            output::print_archived(&archived, format);
+            return;
+        }
+        if archived.is_empty() {
+            println!("{}", t!("archive.none").paint(Role::Warning));
+            return;
+        }
+
+        println!("\n{}", t!("archive.title").paint(Role::Title));
+        println!("{}", "=".repeat(70).paint(Role::Muted));
+        for habit in archived {
+            println!("  {}", render::text(&t!("archive.line",
+                name = habit.name.paint(Role::Name),
+                id = format!("#{}", habit.id).paint(Role::Muted),
+                goal = format!("[{}]", habit.goal.display()).paint(Role::Muted),
+                date = config::format_date(habit.archived_date.unwrap()),
+                total = habit.completions.len(),
+                best = habit.longest_streak(),
+            )));
+        }
+        println!();
+    }
+
+    fn show_habits(&self, filter_category: Option<&str>, filter_tags: Option<&TagExpr>, format: OutputFormat) {
+        let mut filtered_habits: Vec<_> = self.habits.values()
+            .filter(|h| !h.is_archived())
             .filter(|h| {
-                filter_category.map_or(true, |cat| {
-                    h.category.as_ref().map_or(false, |c| c == cat)
-                })
+                filter_category.is_none_or(|cat| h.category.as_deref() == Some(cat))
             })
+            .filter(|h| filter_tags.is_none_or(|expr| expr.matches(&h.tags)))
             .collect();
 
+        if format != OutputFormat::Text {
+            filtered_habits.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+            output::print_habits(&filtered_habits, format);
+            return;
+        }
+
         if filtered_habits.is_empty() {
             if let Some(cat) = filter_category {
-                println!("\n{}", format!("📋 No habits in category '{}'", cat).yellow());
+                println!("\n{}", t!("list.empty_category", category = cat).paint(Role::Warning));
+            } else if filter_tags.is_some() {
+                println!("\n{}", t!("list.no_tag_match").paint(Role::Warning));
             } else {
-                println!("\n{}", "📋 No habits tracked yet. Add one with 'add <habit_name>'".yellow());
+                println!("\n{}", t!("list.empty").paint(Role::Warning));
             }
             return;
         }
 
-        println!("\n{}", "📊 YOUR HABITS".bright_cyan().bold());
-        println!("{}", "=".repeat(70).bright_black());
+        println!("\n{}", t!("list.title").paint(Role::Title));
+        println!("{}", "=".repeat(70).paint(Role::Muted));
 
         let mut by_category: HashMap<String, Vec<&Habit>> = HashMap::new();
         for habit in &filtered_habits {
-            let cat = habit.category.clone().unwrap_or_else(|| "Uncategorized".to_string());
-            by_category.entry(cat).or_insert_with(Vec::new).push(habit);
+            let cat = habit.category.clone().unwrap_or_else(|| t!("list.uncategorized"));
+            by_category.entry(cat).or_default().push(habit);
         }
 
         let mut categories: Vec<_> = by_category.keys().collect();
         categories.sort();
 
         for category in categories {
-            println!("\n{}", format!("📁 {}", category).bright_magenta().bold());
+            match self.category_goal_status(category) {
+                Some(status) => println!("\n{}  {}", self.category_label(category), status),
+                None => println!("\n{}", self.category_label(category)),
+            }
 
             let mut habits = by_category[category].clone();
-            habits.sort_by(|a, b| b.current_streak().cmp(&a.current_streak()));
+            habits.sort_by_key(|h| std::cmp::Reverse(h.current_streak()));
 
             for habit in habits {
-                let done_today = if habit.is_done_today() { "✓".green() } else { " ".normal() };
+                let done_today = if habit.is_done_today() { render::glyph("✓", "x").paint(Role::Success) } else { " ".to_string() };
                 let streak = habit.current_streak();
                 let longest = habit.longest_streak();
                 let (weekly, target) = habit.goal_progress_this_week();
-                let progress = habit.progress_bar(target);
+                let progress = Habit::progress_bar(weekly, target);
                 let status = habit.goal_status();
 
-                println!(
-                    "\n  [{}] {} {} (🔥 {} day{} | 🏆 best: {})",
-                    done_today,
-                    habit.name.bright_white().bold(),
-                    format!("[{}]", habit.goal.display()).bright_black(),
-                    streak,
-                    if streak == 1 { "" } else { "s" },
-                    longest
-                );
-                println!("      Week:  {} {}/{}  {}", progress, weekly, target, status);
-                println!("      Month: {} completions", habit.monthly_count());
+                println!("\n  {}", render::text(&tn!("list.habit", streak,
+                    done = done_today,
+                    name = habit.name.paint(Role::Name),
+                    id = format!("#{}", habit.id).paint(Role::Muted),
+                    goal = format!("[{}]", habit.goal.display()).paint(Role::Muted),
+                    icon = render::glyph("🔥", "streak"),
+                    best = longest,
+                )));
+                println!("      {}", t!("list.week", bar = progress, current = weekly, target = target, status = status));
+                println!("      {}", tn!("list.month", habit.monthly_count()));
+
+                if !habit.tags.is_empty() {
+                    let label = if render::is_plain() { t!("list.tags") } else { "🏷  ".to_string() };
+                    println!("      {}{}", label, habit.tags.join(", ").paint(Role::Accent));
+                }
+
+                if let Some(pause) = habit.active_pause() {
+                    println!("      {}", render::text(&t!("list.paused_until", date = config::format_date(pause.to).paint(Role::Paused))));
+                }
 
                 if let Some(ref time) = habit.reminder_time {
-                    println!("      ⏰ Reminder: {}", time.bright_yellow());
+                    println!("      {}", render::text(&t!("list.reminder", time = time.paint(Role::Reminder))));
                 }
             }
         }
         println!();
     }
 
-    fn show_stats(&self, name: &str) {
-        if let Some(habit) = self.habits.get(name) {
-            println!("\n{}", format!("📈 STATS FOR: {}", habit.name).bright_cyan().bold());
-            println!("{}", "=".repeat(50).bright_black());
-            println!("{} {}", "🎯 Goal:".bold(), habit.goal.display());
-            println!("{} {}", "🔥 Current streak:".bold(), format!("{} days", habit.current_streak()).bright_yellow());
-            println!("{} {}", "🏆 Longest streak:".bold(), format!("{} days", habit.longest_streak()).bright_green());
-            println!("{} {}", "📅 Created:".bold(), habit.created_date);
-            println!("{} {}", "✅ Total completions:".bold(), habit.completions.len());
-            println!("{} {}", "📊 Last 7 days:".bold(), habit.weekly_count());
-            println!("{} {}", "📊 Last 30 days:".bold(), habit.monthly_count());
+    fn show_stats(&self, name: &str, format: OutputFormat) {
+        if let Some(habit) = self.lookup(name) {
+            if format != OutputFormat::Text {
+                output::print_habit_details(habit, format);
+                return;
+            }
+            println!("\n{}", t!("stats.title", name = habit.name).paint(Role::Title));
+            println!("{}", "=".repeat(50).paint(Role::Muted));
+            println!("{} {}", t!("stats.id").paint(Role::Label), habit.id.paint(Role::Muted));
+            println!("{} {}", t!("stats.goal").paint(Role::Label), habit.goal.display());
+            if habit.goal_history.len() > 1 {
+                let changes: Vec<String> = habit.goal_history.iter()
+                    .map(|c| t!("stats.goal_change", goal = c.goal.display(), date = config::format_date(c.from)))
+                    .collect();
+                println!("{} {}", t!("stats.goal_history").paint(Role::Label), changes.join(", ").paint(Role::Muted));
+            }
+            println!("{} {}", t!("stats.current_streak").paint(Role::Label), tn!("common.days", habit.current_streak()).paint(Role::Streak));
+            println!("{} {}", t!("stats.longest_streak").paint(Role::Label), tn!("common.days", habit.longest_streak()).paint(Role::Record));
+            println!("{} {}", t!("stats.created").paint(Role::Label), config::format_date(habit.created_date));
+            println!("{} {}", t!("stats.total").paint(Role::Label), habit.completions.len());
+            println!("{} {}", t!("stats.last_7_days").paint(Role::Label), habit.weekly_count());
+            println!("{} {}", t!("stats.last_30_days").paint(Role::Label), habit.monthly_count());
 
             if let Some(ref cat) = habit.category {
-                println!("{} {}", "📁 Category:".bold(), cat.bright_magenta());
+                println!("{} {}", t!("stats.category").paint(Role::Label), cat.paint(Role::Accent));
+            }
+
+            if !habit.tags.is_empty() {
+                println!("{} {}", t!("stats.tags").paint(Role::Label), habit.tags.join(", ").paint(Role::Accent));
             }
 
             if let Some(ref time) = habit.reminder_time {
-                println!("{} {}", "⏰ Reminder:".bold(), time.bright_yellow());
+                println!("{} {}", t!("stats.reminder").paint(Role::Label), time.paint(Role::Reminder));
             }
 
-            let (current, target) = habit.goal_progress_this_week();
-            let completion_rate = (current as f32 / target as f32 * 100.0) as u32;
-            println!("{} {}%", "📈 This week completion:".bold(),
-                if completion_rate >= 100 { format!("{}", completion_rate).green() }
-                else if completion_rate >= 70 { format!("{}", completion_rate).yellow() }
-                else { format!("{}", completion_rate).red() }
-            );
+            if let Some(pause) = habit.active_pause() {
+                println!("{} {} {} {}", t!("stats.paused").paint(Role::Label), config::format_date(pause.from), render::glyph("→", "->"), config::format_date(pause.to).paint(Role::Paused));
+            }
+
+            if let Some(date) = habit.archived_date {
+                println!("{} {}", t!("stats.archived").paint(Role::Label), config::format_date(date).paint(Role::Muted));
+            }
+
+            let (current, target) = habit.goal_progress_this_week();
+            let completion_rate = (current as f32 / target.max(1) as f32 * 100.0) as u32;
+            println!("{} {}%", t!("stats.week_rate").paint(Role::Label),
+                completion_rate.to_string().paint(render::progress_role(completion_rate)));
 
             if !habit.completions.is_empty() {
-                println!("\n{}", "Recent completions:".bold());
+                println!("\n{}", t!("stats.recent").paint(Role::Label));
                 for date in habit.completions.iter().rev().take(10) {
-                    println!("  • {}", date.format("%Y-%m-%d (%A)").to_string().bright_white());
+                    println!("  {} {}", render::glyph("•", "-"), format!("{} ({})", config::format_date(*date), i18n::weekday_name(date.weekday())).paint(Role::Value));
                 }
             }
             println!();
-        } else {
-            println!("{}", format!("❌ Habit '{}' not found!", name).red());
         }
     }
 
-    fn show_calendar(&self, name: &str) {
-        if let Some(habit) = self.habits.get(name) {
-            let today = Local::now().naive_local().date();
-            println!("\n{}", format!("📅 CALENDAR FOR: {}", habit.name).bright_cyan().bold());
-            println!("{}", habit.get_calendar_month(today.year(), today.month()));
-            println!("{}", "  ● Days with completion shown in green".bright_black());
+    fn show_calendar(&self, name: &str, (year, month): (i32, u32), browse: bool, format: OutputFormat) {
+        let Some(habit) = self.lookup(name) else {
+            return;
+        };
+        if format != OutputFormat::Text {
+            output::print_calendar(habit, year, month, format);
+            return;
+        }
+        println!("\n{}", t!("calendar.title", name = habit.name).paint(Role::Title));
+        if browse && io::stdin().is_terminal() && io::stdout().is_terminal() {
+            if let Err(e) = tui::browse_calendar(habit, year, month) {
+                fail(t!("calendar.browse_failed", error = e));
+            }
+            return;
+        }
+        print!("{}", calendar_page(habit, year, month));
+    }
+
+    // Every active habit, or those in `category`, against each day from
+    // `first` to `last`.
+    fn show_grid(&self, first: NaiveDate, last: NaiveDate, monthly: bool, category: Option<&str>) {
+        let mut habits: Vec<&Habit> = self.habits.values()
+            .filter(|h| !h.is_archived())
+            .filter(|h| category.is_none_or(|cat| h.category.as_deref() == Some(cat)))
+            .collect();
+        if habits.is_empty() {
+            match category {
+                Some(cat) => println!("\n{}", t!("list.empty_category", category = cat).paint(Role::Warning)),
+                None => println!("\n{}", t!("list.empty").paint(Role::Warning)),
+            }
+            return;
+        }
+        habits.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+
+        let title = if monthly {
+            t!("grid.month_title", month = i18n::month_name(first.month()), year = first.year())
         } else {
-            println!("{}", format!("❌ Habit '{}' not found!", name).red());
+            t!("grid.week_title", from = config::format_date(first), to = config::format_date(last))
+        };
+        println!("\n{}", title.paint(Role::Title));
+        if let Some(cat) = category {
+            println!("{}", self.category_label(cat));
+        }
+        print!("{}", get_habit_grid(&habits, first, last));
+        let legend = if render::is_plain() { t!("grid.legend_plain") } else { t!("grid.legend") };
+        println!("\n  {}", legend.paint(Role::Muted));
+    }
+
+    // The heatmap for one habit, or all active habits together when `name`
+    // is None, over `year` or else the 53 weeks up to today.
+    fn show_heatmap(&self, name: Option<&str>, year: Option<i32>) {
+        let habits: Vec<&Habit> = match name {
+            Some(name) => match self.lookup(name) {
+                Some(habit) => vec![habit],
+                None => return,
+            },
+            None => self.habits.values().filter(|h| !h.is_archived()).collect(),
+        };
+        if habits.is_empty() {
+            println!("\n{}", t!("list.empty").paint(Role::Warning));
+            return;
+        }
+
+        let today = Local::now().naive_local().date();
+        let (first, last, period) = match year {
+            Some(year) => (
+                NaiveDate::from_ymd_opt(year, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(year, 12, 31).unwrap(),
+                year.to_string(),
+            ),
+            None => {
+                let first = config::current().week_start.week_of(today) - chrono::Duration::weeks(52);
+                (first, today, t!("heatmap.past_year"))
+            }
+        };
+        let title = match name {
+            Some(_) => habits[0].name.clone(),
+            None => t!("heatmap.all_habits"),
+        };
+        println!("\n{}", t!("heatmap.title", name = title, period = period).paint(Role::Title));
+        print!("{}", get_heatmap(&habits, first, last));
+
+        let total: usize = habits.iter()
+            .map(|h| h.completions.iter().filter(|&&d| d >= first && d <= last).count())
+            .sum();
+        println!("\n  {}", tn!("heatmap.total", total).paint(Role::Label));
+    }
+
+    // Writes one habit's charts to an SVG file: the completion rate of each
+    // of the last 52 weeks, completions in each of the last 26 weeks
+    // against the goal, and the past year day by day.
+    fn write_chart(&self, name: &str, kinds: &[ChartKind], path: Option<&str>) {
+        let Some(habit) = self.lookup(name) else {
+            return;
+        };
+        let week_start = config::current().week_start;
+        let today = Local::now().naive_local().date();
+        let this_week = week_start.week_of(today);
+        let first_week = (this_week - chrono::Duration::weeks(51)).max(week_start.week_of(habit.created_date));
+        let weeks: Vec<NaiveDate> = first_week.iter_weeks().take_while(|w| *w <= this_week).collect();
+        // Weeks are labelled with the month that starts in them.
+        let label = |week: NaiveDate| week.iter_days().take(7).find(|d| d.day() == 1)
+            .map_or_else(String::new, |d| i18n::format_date(d, "%b"));
+        let progress = |week: NaiveDate| habit.progress_between(week, week + chrono::Duration::days(6));
+
+        let charts: Vec<(String, svg::Chart)> = kinds.iter().map(|kind| match kind {
+            ChartKind::Rate => {
+                // Weeks paused throughout have no target and no rate.
+                let points: Vec<(String, u32)> = weeks.iter()
+                    .map(|&week| (week, progress(week)))
+                    .filter(|&(_, (_, target))| target > 0)
+                    .map(|(week, (count, target))| (label(week), (count * 100 / target).min(100) as u32))
+                    .collect();
+                (t!("chart.rate"), svg::line_chart(&points))
+            }
+            ChartKind::Weekly => {
+                let bars: Vec<(String, usize, bool)> = weeks.iter().rev().take(26).rev()
+                    .map(|&week| {
+                        let (count, target) = progress(week);
+                        (label(week), count, count >= target)
+                    })
+                    .collect();
+                (t!("chart.weekly"), svg::bar_chart(&bars))
+            }
+            ChartKind::Heatmap => {
+                let days: Vec<(NaiveDate, svg::Day)> = (this_week - chrono::Duration::weeks(52)).iter_days()
+                    .take_while(|d| *d <= today)
+                    .map(|d| (d, habit.day_state(d, today)))
+                    .collect();
+                (t!("chart.heatmap"), svg::heatmap(&days, week_start))
+            }
+        }).collect();
+
+        let path = path.map_or_else(|| format!("{}_chart.svg", habit.id), str::to_string);
+        match fs::write(&path, svg::stack(&habit.name, &charts)) {
+            Ok(()) => println!("{}", t!("chart.done", file = path).paint(Role::Success)),
+            Err(e) => fail(t!("chart.failed", error = e)),
         }
     }
 
-    fn export_csv(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn export_csv(&self, filename: &str, mode: ExportMode) -> Result<(), Box<dyn std::error::Error>> {
         let mut wtr = csv::Writer::from_path(filename)?;
+        match mode {
+            ExportMode::Summary => self.write_summary(&mut wtr)?,
+            ExportMode::Completions => self.write_completions(&mut wtr)?,
+            ExportMode::Matrix => self.write_matrix(&mut wtr)?,
+        }
+        wtr.flush()?;
+        println!("{}", t!("export.done", file = filename).paint(Role::Celebrate));
+        Ok(())
+    }
 
-        wtr.write_record(&[
+    fn write_summary(&self, wtr: &mut csv::Writer<fs::File>) -> csv::Result<()> {
+        wtr.write_record([
             "Habit Name",
             "Category",
             "Goal",
@@ -446,52 +1368,299 @@ impl HabitTracker {
             "Weekly Count",
             "Monthly Count",
             "Completion Rate (%)",
+            "Archived",
+            "ID",
         ])?;
 
         for habit in self.habits.values() {
             let (current, target) = habit.goal_progress_this_week();
             let rate = (current as f32 / target as f32 * 100.0) as u32;
 
-            wtr.write_record(&[
+            wtr.write_record([
                 &habit.name,
                 &habit.category.clone().unwrap_or_else(|| "None".to_string()),
-                &habit.goal.display(),
+                &habit.goal.label(),
                 &habit.created_date.to_string(),
                 &habit.current_streak().to_string(),
                 &habit.longest_streak().to_string(),
                 &habit.completions.len().to_string(),
-                &habit.weekly_count().to_string(),
+                &current.to_string(),
                 &habit.monthly_count().to_string(),
                 &rate.to_string(),
+                &habit.archived_date.map_or_else(|| "No".to_string(), |d| d.to_string()),
+                &habit.id,
             ])?;
         }
+        Ok(())
+    }
 
-        wtr.flush()?;
-        println!("{}", format!("✅ Exported to '{}'", filename).green().bold());
+    // One row per completion, oldest first, archived habits included.
+    fn write_completions(&self, wtr: &mut csv::Writer<fs::File>) -> csv::Result<()> {
+        wtr.write_record(["Date", "Habit Name", "ID", "Category", "Goal"])?;
+        let mut rows: Vec<(NaiveDate, &Habit)> = self.habits.values()
+            .flat_map(|h| h.completions.iter().map(move |&date| (date, h)))
+            .collect();
+        rows.sort_by(|a, b| (a.0, &a.1.name).cmp(&(b.0, &b.1.name)));
+        for (date, habit) in rows {
+            wtr.write_record([
+                &date.to_string(),
+                &habit.name,
+                &habit.id,
+                habit.category.as_deref().unwrap_or(""),
+                &habit.goal_on(date).label(),
+            ])?;
+        }
         Ok(())
     }
 
-    fn list_categories(&self) {
-        let mut categories: Vec<String> = self.habits.values()
+    // A row per day from the first habit's creation (or an earlier
+    // completion) until today, and a column per habit: 1 for a completion,
+    // 0 for none and empty before the habit existed.
+    fn write_matrix(&self, wtr: &mut csv::Writer<fs::File>) -> csv::Result<()> {
+        let mut habits: Vec<&Habit> = self.habits.values().collect();
+        habits.sort_by(|a, b| a.name.cmp(&b.name));
+        wtr.write_record(std::iter::once("Date").chain(habits.iter().map(|h| h.name.as_str())))?;
+
+        let today = Local::now().naive_local().date();
+        let starts = habits.iter().map(|h| h.completions.first().map_or(h.created_date, |&d| d.min(h.created_date)));
+        let Some(first) = starts.min() else {
+            return Ok(());
+        };
+        let last = habits.iter().filter_map(|h| h.completions.last().copied()).fold(today, NaiveDate::max);
+        for date in first.iter_days().take_while(|d| *d <= last) {
+            let cells = habits.iter().map(|h| {
+                if h.completions.contains(&date) {
+                    "1"
+                } else if date < h.created_date {
+                    ""
+                } else {
+                    "0"
+                }
+            });
+            wtr.write_record(std::iter::once(date.to_string()).chain(cells.map(str::to_string)))?;
+        }
+        Ok(())
+    }
+
+    // Every known category: those with settings plus those used by habits.
+    fn category_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.habits.values()
             .filter_map(|h| h.category.clone())
+            .chain(self.categories.keys().cloned())
             .collect();
-        categories.sort();
-        categories.dedup();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn find_category(&self, name: &str) -> Option<String> {
+        let names = self.category_names();
+        names.iter()
+            .find(|c| *c == name)
+            .or_else(|| names.iter().find(|c| c.eq_ignore_ascii_case(name)))
+            .cloned()
+    }
+
+    // Splits leading category-name words off `args`, preferring the longest
+    // run that names an existing category.
+    fn split_category_name(&self, args: &[&str]) -> Option<(String, usize)> {
+        (1..=args.len()).rev()
+            .find_map(|k| self.find_category(&args[..k].join(" ")).map(|c| (c, k)))
+    }
+
+    fn category_label(&self, name: &str) -> String {
+        let settings = self.categories.get(name);
+        let icon = settings.and_then(|c| c.icon.as_deref()).unwrap_or("📁");
+        let label = format!("{} {}", icon, name);
+        match settings.and_then(|c| c.color.as_deref()).and_then(|c| c.parse::<Color>().ok()) {
+            Some(color) => label.paint_color(color),
+            None => label.paint(Role::Group),
+        }
+    }
+
+    // (habits meeting their weekly goal, habits counted, goal %) for a category
+    // with a goal. Archived habits and habits paused today aren't counted.
+    fn category_goal_progress(&self, name: &str) -> Option<(usize, usize, u32)> {
+        let goal = self.categories.get(name)?.goal_percent?;
+        let habits: Vec<&Habit> = self.habits.values()
+            .filter(|h| h.category.as_deref() == Some(name))
+            .filter(|h| !h.is_archived() && h.active_pause().is_none())
+            .collect();
+        let met = habits.iter()
+            .filter(|h| {
+                let (current, target) = h.goal_progress_this_week();
+                current >= target
+            })
+            .count();
+        Some((met, habits.len(), goal))
+    }
+
+    fn category_goal_status(&self, name: &str) -> Option<String> {
+        let (met, total, goal) = self.category_goal_progress(name)?;
+        let percentage = (met as f32 / total.max(1) as f32 * 100.0) as u32;
+        let text = t!("category.goal_status", goal = goal, met = met, total = total);
+        // Thresholds are relative to the category's goal.
+        let role = render::progress_role(percentage * 100 / goal.max(1));
+        Some(format!("{} {}", render::status_mark(role), text).paint(role))
+    }
+
+    fn list_categories(&self, format: OutputFormat) {
+        if format != OutputFormat::Text {
+            output::print_categories(self, format);
+            return;
+        }
+        let categories = self.category_names();
 
         if categories.is_empty() {
-            println!("{}", "📁 No categories defined yet.".yellow());
+            println!("{}", t!("category.none").paint(Role::Warning));
         } else {
-            println!("\n{}", "📁 CATEGORIES:".bright_magenta().bold());
+            println!("\n{}", t!("category.title").paint(Role::Group));
             for cat in categories {
                 let count = self.habits.values()
-                    .filter(|h| h.category.as_ref() == Some(&cat))
+                    .filter(|h| !h.is_archived() && h.category.as_ref() == Some(&cat))
                     .count();
-                println!("  • {} ({} habit{})", cat.bright_white(), count, if count == 1 { "" } else { "s" });
+                print!("  {} {}", self.category_label(&cat), tn!("category.habit_count", count));
+                match self.category_goal_status(&cat) {
+                    Some(status) => println!("  {}", status),
+                    None => println!(),
+                }
             }
             println!();
         }
     }
 
+    fn rename_category(&mut self, old: &str, new: &str) {
+        let Some(old) = self.find_category(old) else {
+            fail(t!("category.not_found", name = old));
+            return;
+        };
+        if self.find_category(new).is_some_and(|c| c != old) {
+            fail(t!("category.exists", name = new));
+            return;
+        }
+        let count = self.move_category(&old, new);
+        if let Some(settings) = self.categories.remove(&old) {
+            self.categories.insert(new.to_string(), settings);
+        }
+        println!("{}", tn!("category.renamed", count, old = old, new = new).paint(Role::Success));
+    }
+
+    fn merge_category(&mut self, from: &str, into: &str) {
+        let Some(from) = self.find_category(from) else {
+            fail(t!("category.not_found", name = from));
+            return;
+        };
+        let into = self.find_category(into).unwrap_or_else(|| into.to_string());
+        if from == into {
+            fail(t!("category.merge_into_itself"));
+            return;
+        }
+        let count = self.move_category(&from, &into);
+        // The target keeps its own settings; it inherits only what it lacks.
+        if let Some(settings) = self.categories.remove(&from) {
+            let target = self.categories.entry(into.clone()).or_default();
+            target.color = target.color.take().or(settings.color);
+            target.icon = target.icon.take().or(settings.icon);
+            target.goal_percent = target.goal_percent.or(settings.goal_percent);
+        }
+        println!("{}", tn!("category.merged", count, from = from, into = into).paint(Role::Success));
+    }
+
+    fn delete_category(&mut self, name: &str) {
+        let Some(name) = self.find_category(name) else {
+            fail(t!("category.not_found", name = name));
+            return;
+        };
+        let mut count = 0;
+        for habit in self.habits.values_mut().filter(|h| h.category.as_deref() == Some(name.as_str())) {
+            habit.category = None;
+            count += 1;
+        }
+        self.categories.remove(&name);
+        println!("{}", tn!("category.deleted", count, name = name).paint(Role::Success));
+    }
+
+    fn move_category(&mut self, from: &str, to: &str) -> usize {
+        let mut count = 0;
+        for habit in self.habits.values_mut().filter(|h| h.category.as_deref() == Some(from)) {
+            habit.category = Some(to.to_string());
+            count += 1;
+        }
+        count
+    }
+
+    fn configure_category(&mut self, name: &str, change: CategoryChange) {
+        let Some(name) = self.find_category(name) else {
+            fail(t!("category.not_found", name = name));
+            return;
+        };
+        let settings = self.categories.entry(name.clone()).or_default();
+        let message = match change {
+            CategoryChange::Color(color) => {
+                let message = color.as_ref().map_or(t!("category.color_cleared"), |c| t!("category.color", color = c));
+                settings.color = color;
+                message
+            }
+            CategoryChange::Icon(icon) => {
+                let message = icon.as_ref().map_or(t!("category.icon_cleared"), |i| t!("category.icon", icon = i));
+                settings.icon = icon;
+                message
+            }
+            CategoryChange::Goal(goal) => {
+                let message = goal.map_or(t!("category.goal_cleared"), |g| t!("category.goal", percent = g));
+                settings.goal_percent = goal;
+                message
+            }
+        };
+        println!("{}", t!("category.updated", name = name, change = message).paint(Role::Success));
+    }
+
+    fn list_tags(&self, format: OutputFormat) {
+        let mut active: Vec<&Habit> = self.habits.values().filter(|h| !h.is_archived()).collect();
+        active.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut by_tag: HashMap<String, Vec<&Habit>> = HashMap::new();
+        for habit in &active {
+            for tag in &habit.tags {
+                by_tag.entry(tag.to_lowercase()).or_default().push(habit);
+            }
+        }
+
+        // Each tag as spelled on the first habit that carries it.
+        let mut tags: Vec<(String, Vec<&Habit>)> = by_tag.into_iter()
+            .map(|(key, habits)| (habits[0].tags.iter().find(|t| t.to_lowercase() == key).unwrap().clone(), habits))
+            .collect();
+        tags.sort_by_key(|(label, _)| label.to_lowercase());
+
+        if format != OutputFormat::Text {
+            output::print_tags(&tags, format);
+            return;
+        }
+        if tags.is_empty() {
+            println!("{}", t!("tag.none").paint(Role::Warning));
+            return;
+        }
+
+        println!("\n{}", t!("tag.title").paint(Role::Group));
+        for (label, habits) in tags {
+            let (current, target) = habits.iter()
+                .map(|h| h.goal_progress_this_week())
+                .fold((0, 0), |(c, t), (hc, ht)| (c + hc, t + ht));
+            let rate = (current as f32 / target.max(1) as f32 * 100.0) as u32;
+            let completions: usize = habits.iter().map(|h| h.completions.len()).sum();
+            let best = habits.iter().map(|h| h.current_streak()).max().unwrap_or(0);
+
+            println!("  {}", render::text(&tn!("tag.line", habits.len(),
+                tag = label.paint(Role::Value),
+                current = current,
+                target = target,
+                rate = rate,
+                total = completions,
+                best = best,
+            )));
+        }
+        println!();
+    }
+
     #[cfg(not(target_os = "windows"))]
     fn show_notification(&self, title: &str, body: &str) {
         use notify_rust::Notification;
@@ -504,7 +1673,7 @@ impl HabitTracker {
 
     #[cfg(target_os = "windows")]
     fn show_notification(&self, _title: &str, _body: &str) {
-        println!("📢 Notification: {} - {}", _title, _body);
+        println!("{}", render::text(&t!("reminder.notification", title = _title, body = _body)));
     }
 
     fn check_reminders(&self) {
@@ -512,202 +1681,938 @@ impl HabitTracker {
         let current_time = now.format("%H:%M").to_string();
 
         for habit in self.habits.values() {
-            if let Some(ref reminder) = habit.reminder_time {
-                if reminder == &current_time && !habit.is_done_today() {
-                    #[cfg(not(target_os = "windows"))]
-                    self.show_notification(
-                        "Habit Reminder",
-                        &format!("Time to complete: {}", habit.name),
-                    );
-                    println!("{}",
-                        format!("⏰ Reminder: Time to complete '{}'!", habit.name)
-                        .bright_yellow().bold()
-                    );
-                }
+            if habit.is_archived() || habit.active_pause().is_some() {
+                continue;
+            }
+            if let Some(ref reminder) = habit.reminder_time
+                && reminder == &current_time
+                && !habit.is_done_today()
+            {
+                #[cfg(not(target_os = "windows"))]
+                self.show_notification(
+                    &t!("reminder.due_title"),
+                    &t!("reminder.due_body", name = habit.name),
+                );
+                println!("{}", t!("reminder.due", name = habit.name).paint(Role::Alert));
+            }
+        }
+    }
+}
+
+// A month of `habit`'s calendar with its legend, as shown by `calendar`.
+fn calendar_page(habit: &Habit, year: i32, month: u32) -> String {
+    let legend = if render::colors_enabled() { t!("calendar.legend") } else { t!("calendar.legend_plain") };
+    format!("{}\n  {}\n", habit.get_calendar_month(year, month), legend.paint(Role::Muted))
+}
+
+// Options each command accepts; anything else is rejected before dispatch.
+fn allowed_flags(command: &str) -> &'static [&'static str] {
+    match command {
+        "add" => &["goal", "category"],
+        "edit" => &["name", "goal", "from", "category", "reminder", "created"],
+        "list" | "ls" => &["tags", "category", "format"],
+        "calendar" | "cal" => &["month", "browse", "format"],
+        "heatmap" => &["year"],
+        "grid" => &["category"],
+        "export" => &["mode"],
+        "import" => &["dry-run", "strategy", "map"],
+        "report" => &["week", "month", "format", "out"],
+        "chart" => &["type", "out"],
+        "stats" | "categories" | "cats" | "tags" | "archived" => &["format"],
+        "pause" => &["from", "to"],
+        _ => &[],
+    }
+}
+
+// Splits a goal such as "4 weekly" or "3 per week" off the end of `words`.
+fn split_trailing_goal(words: &[&str]) -> (String, Option<GoalType>) {
+    for len in (1..=3.min(words.len())).rev() {
+        let (name, goal) = words.split_at(words.len() - len);
+        if let Some(goal) = GoalType::from_string(&goal.join(" ")) {
+            return (name.join(" "), Some(goal));
+        }
+    }
+    (words.join(" "), None)
+}
+
+fn parse_edit(field: &str, args: &[&str]) -> Result<HabitEdit, String> {
+    let today = Local::now().naive_local().date();
+    let parse_date = |s: &str| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|_| t!("error.invalid_date", value = s))
+    };
+    let optional = |args: &[&str]| {
+        let value = args.join(" ");
+        if value.eq_ignore_ascii_case("none") { None } else { Some(value) }
+    };
+
+    match field {
+        "name" => Ok(HabitEdit::Name(args.join(" "))),
+        "goal" => {
+            let (goal_args, from) = match args {
+                [goal @ .., "from", date] => (goal, parse_date(date)?),
+                _ => (args, today),
+            };
+            GoalType::from_string(&goal_args.join(" "))
+                .map(|goal| HabitEdit::Goal(goal, from))
+                .ok_or_else(|| t!("error.invalid_goal"))
+        }
+        "category" => Ok(HabitEdit::Category(optional(args))),
+        "reminder" => {
+            let time = optional(args);
+            if let Some(ref t) = time {
+                chrono::NaiveTime::parse_from_str(t, "%H:%M")
+                    .map_err(|_| t!("error.invalid_time", value = t))?;
+            }
+            Ok(HabitEdit::Reminder(time))
+        }
+        "created" => {
+            let date = parse_date(args[0])?;
+            if date > today {
+                return Err(t!("edit.created_in_future"));
             }
+            Ok(HabitEdit::Created(date))
         }
+        _ => Err(t!("edit.unknown_field", field = field)),
+    }
+}
+
+fn show_config() {
+    let current = config::current();
+    println!("\n{}", t!("config.title").paint(Role::Title));
+    println!("{}", "=".repeat(60).paint(Role::Muted));
+    let colors = current.theme.colors.keys().map(|role| format!("theme.colors.{}", role));
+    let keys = config::KEYS.iter().filter(|k| !k.ends_with("<role>")).map(|k| k.to_string()).chain(colors);
+    for key in keys {
+        println!("  {:<22} {}", key.paint(Role::Label), current.get(&key).unwrap_or_default().paint(Role::Value));
+    }
+    println!("\n  {}\n", t!("config.hint", file = config::CONFIG_FILE).paint(Role::Muted));
+}
+
+// Saves and applies a changed configuration. A new data file gets the habits
+// before the setting points at it, so a path that can't be written never
+// replaces a working one. If the file is already there it's loaded instead,
+// and never overwritten with the habits loaded now.
+fn update_config(tracker: &mut HabitTracker, key: &str, updated: Result<Config, String>) {
+    let current = config::current().data_file;
+    let switch_data = |config: &Config| {
+        if config.data_file == current {
+            return Ok(None);
+        }
+        match fs::read_to_string(&config.data_file) {
+            Ok(data) => serde_json::from_str::<HabitTracker>(&data)
+                .map(|_| Some(true))
+                .map_err(|e| t!("config.data_file_invalid", file = config.data_file, error = e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => tracker.write_to(&config.data_file)
+                .map(|()| Some(false))
+                .map_err(|e| t!("config.write_failed", file = config.data_file, error = e)),
+            Err(e) => Err(t!("config.read_failed", file = config.data_file, error = e)),
+        }
+    };
+    // A new calendar file is written before it's kept, for the same reason.
+    let write_calendar = |config: &Config| match config.calendar_file.as_str() {
+        "" => Ok(()),
+        file => ics::write(tracker, file).map_err(|e| t!("config.write_failed", file = file, error = e)),
+    };
+    match updated.and_then(|config| {
+        let switched = switch_data(&config)?;
+        if key == "calendar_file" {
+            write_calendar(&config)?;
+        }
+        config.save()?;
+        config::apply(config)?;
+        Ok(switched)
+    }) {
+        Ok(switched) => {
+            let value = config::current().get(key).unwrap_or_default();
+            println!("{}", format!("✅ {} = {}", key, value).paint(Role::Success));
+            // Some(true) when the file was already there and is loaded now.
+            if let Some(loaded) = switched {
+                let message = if loaded {
+                    *tracker = HabitTracker::load();
+                    t!("config.data_file_loaded", file = value)
+                } else {
+                    t!("config.data_file_moved", file = value)
+                };
+                println!("{}", message.paint(Role::Info));
+            } else if key == "calendar_file" && !value.is_empty() {
+                println!("{}", t!("config.calendar_written", file = value).paint(Role::Info));
+            }
+        }
+        Err(msg) => fail(format!("❌ {}", msg)),
+    }
+}
+
+// Shows every role in the current theme, or in a preset to try it out.
+fn show_theme(preset: Option<&str>) {
+    let theme = match preset.map(|name| Theme::new(name, &BTreeMap::new())) {
+        Some(Ok(theme)) => Some(theme),
+        Some(Err(msg)) => {
+            fail(format!("❌ {}", msg));
+            return;
+        }
+        None => None,
+    };
+
+    let title = preset.map_or(t!("theme.current"), |name| t!("theme.preview", name = name));
+    println!("\n{}", title.paint(Role::Title));
+    println!("{}", "=".repeat(60).paint(Role::Muted));
+    for row in Role::ALL.chunks(5) {
+        let samples: Vec<String> = row.iter()
+            .map(|&role| {
+                let sample = format!("{:<11}", role.name());
+                match &theme {
+                    Some(theme) => sample.paint_style(theme.style(role)),
+                    None => sample.paint(role),
+                }
+            })
+            .collect();
+        println!("  {}", samples.join(""));
     }
+
+    let thresholds = render::thresholds();
+    println!("\n  {}", t!("theme.thresholds", on_track = thresholds.on_track, close = thresholds.close));
+    println!("  {}", t!("theme.presets", presets = theme::PRESETS.join(", ")));
+    println!("  {}\n", t!("theme.switch_hint").paint(Role::Muted));
+}
+
+// "Usage: <syntax>" for a command called with the wrong arguments.
+// Set by `fail`, so a single command can exit with an error status.
+static FAILED: AtomicBool = AtomicBool::new(false);
+
+// Errors go to stderr, keeping stdout to what was asked for, e.g. JSON.
+fn fail(message: String) {
+    FAILED.store(true, Ordering::Relaxed);
+    eprintln!("{}", message.paint(Role::Error));
+}
+
+fn usage(syntax: &str) -> String {
+    t!("common.usage", syntax = syntax).paint(Role::Warning)
 }
 
+// (syntax, message key) for each line of `help`, by section.
+const HELP_BASIC: &[(&str, &str)] = &[
+    ("add <n> [goal]", "help.add"),
+    ("remove <n>", "help.remove"),
+    ("archive <n>", "help.archive"),
+    ("unarchive <n>", "help.unarchive"),
+    ("archived", "help.archived"),
+    ("done <n>", "help.done"),
+    ("list [category]", "help.list"),
+    ("stats <n>", "help.stats"),
+];
+
+const HELP_ADVANCED: &[(&str, &str)] = &[
+    ("category <n> <cat>", "help.category"),
+    ("categories", "help.categories"),
+    ("categories rename|merge <a> <b>", "help.categories_rename"),
+    ("categories delete <cat>", "help.categories_delete"),
+    ("categories color|icon <cat> <value>", "help.categories_style"),
+    ("categories goal <cat> <percent>", "help.categories_goal"),
+    ("tag <n> <tag...>", "help.tag"),
+    ("tags", "help.tags"),
+    ("list --tags <expr>", "help.list_tags"),
+    ("calendar <n> [month]", "help.calendar"),
+    ("heatmap [n] [year]", "help.heatmap"),
+    ("grid [week|month] [-N]", "help.grid"),
+    ("export <file.csv> [--mode m]", "help.export"),
+    ("import <file> [--dry-run]", "help.import"),
+    ("report --week|--month", "help.report"),
+    ("chart <n> [--out f.svg]", "help.chart"),
+    ("--format json|tsv", "help.format"),
+    ("--plain", "help.plain"),
+    ("theme [preset]", "help.theme"),
+    ("config [get|set|unset]", "help.config"),
+    ("tui", "help.tui"),
+    ("remind <n> <HH:MM>", "help.remind"),
+    ("edit <n> <field> <value>", "help.edit"),
+    ("pause <n|all> <from> <to>", "help.pause"),
+    ("resume <n|all>", "help.resume"),
+];
+
+const HELP_OTHER: &[(&str, &str)] = &[
+    ("help", "help.help"),
+    ("quit", "help.quit"),
+];
+
+const HELP_EXAMPLES: &[&str] = &[
+    "add Workout 4 weekly",
+    "add \"Read 2 weekly reports\" --goal \"1 weekly\" --category Work",
+    "category Workout Fitness",
+    "remind Workout 07:00",
+    "pause all 2025-07-01 2025-07-14",
+    "edit Workout goal 5 weekly from 2025-03-01",
+    "export habits_backup.csv",
+    "config set date_format %d.%m.%Y",
+];
+
 fn print_help() {
-    println!("\n{}", "🎯 HABIT TRACKER COMMANDS".bright_cyan().bold());
-    println!("{}", "=".repeat(60).bright_black());
-    println!("{}", "  BASIC COMMANDS:".bright_white().bold());
-    println!("  add <n> [goal]     - Add habit (goal: daily, 4 weekly, 20 monthly)");
-    println!("  remove <n>         - Remove a habit");
-    println!("  done <n>           - Mark habit as done today");
-    println!("  list [category]       - Show all habits (optionally filter by category)");
-    println!("  stats <n>          - Show detailed stats");
-    println!();
-    println!("{}", "  ADVANCED FEATURES:".bright_white().bold());
-    println!("  category <n> <cat> - Set category for a habit");
-    println!("  categories            - List all categories");
-    println!("  calendar <n>       - Show monthly calendar view");
-    println!("  export <file.csv>     - Export all data to CSV");
-    println!("  remind <n> <HH:MM> - Set reminder time (24h format)");
-    println!();
-    println!("{}", "  OTHER:".bright_white().bold());
-    println!("  help                  - Show this help");
-    println!("  quit                  - Exit the program");
-    println!();
-    println!("{}", "  EXAMPLES:".bright_yellow());
-    println!("  add Workout 4 weekly");
-    println!("  category Workout Fitness");
-    println!("  remind Workout 07:00");
-    println!("  export habits_backup.csv");
+    let section = |key: &str, lines: &[(&str, &str)]| {
+        println!("{}", format!("  {}", t!(key)).paint(Role::Heading));
+        for (syntax, key) in lines {
+            println!("  {:<24} - {}", syntax, t!(key));
+        }
+        println!();
+    };
+
+    println!("\n{}", t!("help.title").paint(Role::Title));
+    println!("{}", "=".repeat(60).paint(Role::Muted));
+    section("help.basic", HELP_BASIC);
+    println!("  {}\n", t!("help.habit_names").paint(Role::Muted));
+    section("help.advanced", HELP_ADVANCED);
+    section("help.other", HELP_OTHER);
+    println!("{}", format!("  {}", t!("help.examples")).paint(Role::Heading));
+    for example in HELP_EXAMPLES {
+        println!("  {}", example);
+    }
     println!();
 }
 
 fn main() {
+    // `--plain` applies to the whole session, so it's taken out before the
+    // rest of the arguments are parsed as a command.
+    let (plain, args): (Vec<String>, Vec<String>) = std::env::args().skip(1).partition(|arg| arg == "--plain");
+    render::init(!plain.is_empty());
+    if let Err(msg) = Config::load().and_then(config::apply) {
+        // The defaults always apply, and they pick the language for the warning.
+        let _ = config::apply(Config::default());
+        eprintln!("{}", format!("⚠ {}\n{}", msg, t!("app.default_settings")).paint(Role::Warning));
+    }
+
     let mut tracker = HabitTracker::load();
 
-    println!("\n{}", "🎯 Welcome to Habit Tracker v2.0!".bright_cyan().bold());
-    println!("{}", "Type 'help' for commands\n".bright_black());
+    if args.iter().any(|arg| arg == "--tui") {
+        if let Err(e) = tui::run(&mut tracker) {
+            fail(t!("app.dashboard_failed", error = e));
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `habit_tracker <command> [args...]` runs a single command and exits,
+    // with status 1 if it failed.
+    if let Some(command) = parser::parse_args(&args) {
+        run_command(&mut tracker, &command);
+        if FAILED.load(Ordering::Relaxed) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    println!("\n{}", t!("app.welcome").paint(Role::Title));
+    println!("{}\n", t!("app.help_hint").paint(Role::Muted));
 
     tracker.check_reminders();
 
+    let mut editor = repl::editor().expect("Failed to start line editor");
+
     loop {
-        print!("{} ", ">".bright_green());
-        io::stdout().flush().unwrap();
+        if let Some(helper) = editor.helper_mut() {
+            helper.refresh(&tracker);
+        }
 
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
+        let input = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => {
+                println!("{}", t!("app.goodbye").paint(Role::Title));
+                break;
+            }
+            Err(e) => {
+                fail(t!("app.read_failed", error = e));
+                break;
+            }
+        };
+        if !input.trim().is_empty() {
+            let _ = editor.add_history_entry(input.as_str());
+        }
 
-        let parts: Vec<&str> = input.trim().split_whitespace().collect();
-        if parts.is_empty() {
-            continue;
+        let command = match parser::parse(&input) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(msg) => {
+                fail(format!("❌ {}", msg));
+                continue;
+            }
+        };
+        if !run_command(&mut tracker, &command) {
+            break;
         }
+    }
 
-        match parts[0].to_lowercase().as_str() {
-            "add" => {
-                if parts.len() >= 2 {
-                    let mut name_parts = vec![];
-                    let mut goal = GoalType::Daily;
-                    let mut i = 1;
-
-                    while i < parts.len() {
-                        if let Ok(num) = parts[i].parse::<u32>() {
-                            if i + 1 < parts.len() {
-                                match parts[i + 1].to_lowercase().as_str() {
-                                    "weekly" | "week" => {
-                                        goal = GoalType::Weekly(num);
-                                        break;
-                                    }
-                                    "monthly" | "month" => {
-                                        goal = GoalType::Monthly(num);
-                                        break;
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        } else if parts[i].to_lowercase() == "daily" {
-                            goal = GoalType::Daily;
-                            break;
-                        }
-                        name_parts.push(parts[i]);
-                        i += 1;
-                    }
+    if let Err(e) = editor.save_history(repl::HISTORY_FILE) {
+        println!("{}", t!("app.history_failed", error = e).paint(Role::Warning));
+    }
+}
 
-                    let name = name_parts.join(" ");
-                    tracker.add_habit(name, goal, None);
-                    tracker.save().unwrap();
+// Runs one parsed command. Returns false when the user asked to quit.
+fn run_command(tracker: &mut HabitTracker, command: &parser::Command) -> bool {
+    if let Err(msg) = command.check_flags(allowed_flags(&command.name)) {
+        fail(format!("❌ {}", msg));
+        return true;
+    }
+    // `report` has its own --format for the document it writes.
+    let format_flag = command.flag("format").filter(|_| command.name != "report");
+    let format = match OutputFormat::from_flag(format_flag) {
+        Ok(format) => format,
+        Err(msg) => {
+            fail(format!("❌ {}", msg));
+            return true;
+        }
+    };
+    let words = command.words();
+    let parts: Vec<&str> = std::iter::once(command.name.as_str()).chain(words.iter().copied()).collect();
+
+    match parts[0] {
+        "add" => {
+            if parts.len() >= 2 {
+                // A quoted name is taken as-is; otherwise a goal is only
+                // recognized at the very end so names like
+                // "Read 2 weekly reports" stay intact.
+                let (name, goal) = if command.args[0].quoted {
+                    let rest = parts[2..].join(" ");
+                    let goal = if rest.is_empty() { Some(GoalType::Daily) } else { GoalType::from_string(&rest) };
+                    (parts[1].to_string(), goal)
+                } else if command.has_flag("goal") {
+                    (parts[1..].join(" "), Some(GoalType::Daily))
                 } else {
-                    println!("{}", "Usage: add <habit_name> [goal]".yellow());
-                    println!("{}", "Examples: add Workout, add Reading 4 weekly".bright_black());
+                    let (name, goal) = split_trailing_goal(&parts[1..]);
+                    (name, Some(goal.unwrap_or(GoalType::Daily)))
+                };
+                let goal = match command.flag("goal") {
+                    Some(g) => GoalType::from_string(g),
+                    None => goal,
+                };
+
+                match goal {
+                    Some(goal) if !name.is_empty() => {
+                        tracker.add_habit(name, goal, command.flag("category").map(str::to_string));
+                        tracker.save_or_report();
+                    }
+                    Some(_) => fail(t!("habit.needs_name")),
+                    None => fail(format!("❌ {}", t!("error.invalid_goal"))),
                 }
+            } else {
+                println!("{}", usage("add <habit_name> [goal] [--goal <goal>] [--category <category>]"));
+                println!("{}", t!("common.examples", examples = "add Workout, add Reading 4 weekly, add \"Read 2 weekly reports\" 1 weekly").paint(Role::Muted));
             }
-            "remove" | "rm" => {
-                if parts.len() > 1 {
-                    let name = parts[1..].join(" ");
-                    tracker.remove_habit(&name);
-                    tracker.save().unwrap();
-                } else {
-                    println!("{}", "Usage: remove <habit_name>".yellow());
+        }
+        "remove" | "rm" => {
+            if parts.len() > 1 {
+                let name = parts[1..].join(" ");
+                tracker.remove_habit(&name);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("remove <habit_name>"));
+            }
+        }
+        "edit" if !command.flags.is_empty() && parts.len() > 1 => {
+            let Some(id) = tracker.resolve_or_report(&parts[1..].join(" ")) else {
+                return true;
+            };
+            // Renaming goes last so the other edits still find the habit.
+            let mut edits = Vec::new();
+            for field in ["goal", "category", "reminder", "created", "name"] {
+                let Some(value) = command.flag(field) else {
+                    continue;
+                };
+                let mut args: Vec<&str> = if field == "goal" { value.split_whitespace().collect() } else { vec![value] };
+                if let Some(from) = command.flag("from").filter(|_| field == "goal") {
+                    args.extend(["from", from]);
                 }
+                edits.push(parse_edit(field, &args));
             }
-            "done" | "complete" => {
-                if parts.len() > 1 {
-                    let name = parts[1..].join(" ");
-                    tracker.mark_done(&name);
-                    tracker.save().unwrap();
-                } else {
-                    println!("{}", "Usage: done <habit_name>".yellow());
+            match edits.into_iter().collect::<Result<Vec<_>, _>>() {
+                Ok(edits) if !edits.is_empty() => {
+                    for edit in edits {
+                        tracker.edit_habit(&id, edit);
+                    }
+                    tracker.save_or_report();
                 }
+                Ok(_) => fail(t!("edit.from_without_goal")),
+                Err(msg) => fail(format!("❌ {}", msg)),
             }
-            "list" | "ls" => {
-                let filter = if parts.len() > 1 {
-                    Some(parts[1..].join(" "))
-                } else {
-                    None
-                };
-                tracker.show_habits(filter.as_deref());
+        }
+        "edit" => {
+            let is_field = |p: &str| {
+                matches!(p.to_lowercase().as_str(), "name" | "goal" | "category" | "reminder" | "created")
+            };
+            let field_positions: Vec<usize> = (2..parts.len()).filter(|&i| is_field(parts[i])).collect();
+            // Prefer the split whose leading words actually name a habit.
+            let field_pos = field_positions.iter()
+                .find(|&&i| tracker.find_exact(&parts[1..i].join(" ")).is_some())
+                .or_else(|| field_positions.iter().find(|&&i| tracker.resolve(&parts[1..i].join(" ")).is_ok()))
+                .or(field_positions.first())
+                .copied();
+
+            match field_pos {
+                Some(pos) if pos + 1 < parts.len() => {
+                    let name = parts[1..pos].join(" ");
+                    match parse_edit(&parts[pos].to_lowercase(), &parts[pos + 1..]) {
+                        Ok(edit) => {
+                            tracker.edit_habit(&name, edit);
+                            tracker.save_or_report();
+                        }
+                        Err(msg) => fail(format!("❌ {}", msg)),
+                    }
+                }
+                _ => {
+                    println!("{}", t!("edit.usage").paint(Role::Warning));
+                    println!("{}", t!("edit.fields").paint(Role::Muted));
+                }
             }
-            "stats" => {
-                if parts.len() > 1 {
-                    let name = parts[1..].join(" ");
-                    tracker.show_stats(&name);
-                } else {
-                    println!("{}", "Usage: stats <habit_name>".yellow());
+        }
+        "archive" => {
+            if parts.len() > 1 {
+                let name = parts[1..].join(" ");
+                tracker.archive_habit(&name);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("archive <habit_name>"));
+            }
+        }
+        "unarchive" | "restore" => {
+            if parts.len() > 1 {
+                let name = parts[1..].join(" ");
+                tracker.unarchive_habit(&name);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("unarchive <habit_name>"));
+            }
+        }
+        "archived" => {
+            tracker.show_archived(format);
+        }
+        "done" | "complete" => {
+            if parts.len() > 1 {
+                let name = parts[1..].join(" ");
+                tracker.mark_done(&name);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("done <habit_name>"));
+            }
+        }
+        "list" | "ls" => {
+            if let Some(tags) = command.flag("tags") {
+                // Unquoted expressions spill over into the positional words.
+                let expr = std::iter::once(tags).chain(parts[1..].iter().copied()).collect::<Vec<_>>().join(" ");
+                match TagExpr::parse(&expr) {
+                    Ok(expr) => tracker.show_habits(None, Some(&expr), format),
+                    Err(msg) => {
+                        fail(format!("❌ {}", msg));
+                        println!("{}", t!("common.example", example = "list --tags health and (morning or work) and not weekend").paint(Role::Muted));
+                    }
                 }
+                return true;
             }
-            "category" | "cat" => {
-                if parts.len() > 2 {
-                    let name = parts[1].to_string();
-                    let category = parts[2..].join(" ");
-                    tracker.set_category(&name, category);
-                    tracker.save().unwrap();
+            let filter = if parts.len() > 1 {
+                Some(parts[1..].join(" "))
+            } else {
+                command.flag("category").map(str::to_string)
+            };
+            tracker.show_habits(filter.as_deref(), None, format);
+        }
+        "tag" | "untag" => {
+            if parts.len() > 2 {
+                let (name, used) = tracker.split_habit_name(&parts[1..]);
+                let tags = &parts[1 + used..];
+                if parts[0].eq_ignore_ascii_case("tag") {
+                    tracker.add_tags(&name, tags);
                 } else {
-                    println!("{}", "Usage: category <habit_name> <category>".yellow());
+                    tracker.remove_tags(&name, tags);
                 }
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage(&format!("{} <habit_name> <tag> [tag...]", parts[0].to_lowercase())));
             }
-            "categories" | "cats" => {
-                tracker.list_categories();
+        }
+        "tags" => {
+            tracker.list_tags(format);
+        }
+        "stats" => {
+            if parts.len() > 1 {
+                let name = parts[1..].join(" ");
+                tracker.show_stats(&name, format);
+            } else {
+                println!("{}", usage("stats <habit_name>"));
             }
-            "calendar" | "cal" => {
-                if parts.len() > 1 {
-                    let name = parts[1..].join(" ");
-                    tracker.show_calendar(&name);
-                } else {
-                    println!("{}", "Usage: calendar <habit_name>".yellow());
+        }
+        "category" | "cat" => {
+            if parts.len() > 2 {
+                let (name, used) = tracker.split_habit_name(&parts[1..]);
+                let category = parts[1 + used..].join(" ");
+                tracker.set_category(&name, category);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("category <habit_name> <category>"));
+            }
+        }
+        "categories" | "cats" => {
+            let args = &parts[1.min(parts.len())..];
+            let sub = args.first().map(|s| s.to_lowercase());
+            match sub.as_deref() {
+                None => tracker.list_categories(format),
+                Some("rename") | Some("merge") if args.len() >= 3 => {
+                    let (from, used) = tracker.split_category_name(&args[1..args.len() - 1])
+                        .unwrap_or_else(|| (args[1].to_string(), 1));
+                    let to = args[1 + used..].join(" ");
+                    if sub.as_deref() == Some("rename") {
+                        tracker.rename_category(&from, &to);
+                    } else {
+                        tracker.merge_category(&from, &to);
+                    }
+                    tracker.save_or_report();
+                }
+                Some("delete") | Some("rm") if args.len() >= 2 => {
+                    tracker.delete_category(&args[1..].join(" "));
+                    tracker.save_or_report();
+                }
+                Some("color") | Some("icon") | Some("goal") if args.len() >= 3 => {
+                    let name = args[1..args.len() - 1].join(" ");
+                    let value = args[args.len() - 1];
+                    let value = if value.eq_ignore_ascii_case("none") { None } else { Some(value) };
+                    let change = match sub.as_deref() {
+                        Some("color") => match value {
+                            Some(c) if c.parse::<Color>().is_err() => Err(t!("category.unknown_color", color = c)),
+                            _ => Ok(CategoryChange::Color(value.map(str::to_lowercase))),
+                        },
+                        Some("icon") => Ok(CategoryChange::Icon(value.map(str::to_string))),
+                        _ => match value.map(|v| v.trim_end_matches('%').parse::<u32>()) {
+                            Some(Ok(p)) if (1..=100).contains(&p) => Ok(CategoryChange::Goal(Some(p))),
+                            None => Ok(CategoryChange::Goal(None)),
+                            _ => Err(t!("category.goal_range")),
+                        },
+                    };
+                    match change {
+                        Ok(change) => {
+                            tracker.configure_category(&name, change);
+                            tracker.save_or_report();
+                        }
+                        Err(msg) => fail(format!("❌ {}", msg)),
+                    }
+                }
+                _ => {
+                    println!("{}", t!("category.usage").paint(Role::Warning));
                 }
             }
-            "export" => {
-                let filename = if parts.len() > 1 {
-                    parts[1]
-                } else {
-                    "habits_export.csv"
-                };
-                match tracker.export_csv(filename) {
-                    Ok(_) => {},
-                    Err(e) => println!("{}", format!("❌ Export failed: {}", e).red()),
+        }
+        "calendar" | "cal" => {
+            let today = Local::now().naive_local().date();
+            // The month comes from --month or a trailing "-2", "+1" or
+            // "2025-03", unless that word is part of a habit's name.
+            let (words, month) = match command.flag("month") {
+                Some(arg) => (&parts[1..], Some(parse_month(arg, today).ok_or(arg))),
+                None => match parts[1..].split_last() {
+                    Some((last, rest)) if !rest.is_empty()
+                        && tracker.find_exact(&parts[1..].join(" ")).is_none() =>
+                    {
+                        // "2025-13" and "+10000000" are bad months rather than names
+                        let month_like = (last.len() > 5 && last.as_bytes()[..4].iter().all(u8::is_ascii_digit)
+                            && last.as_bytes()[4] == b'-')
+                            || last.strip_prefix(['-', '+']).is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+                        match parse_month(last, today) {
+                            Some(month) => (rest, Some(Ok(month))),
+                            None if month_like => (rest, Some(Err(*last))),
+                            None => (&parts[1..], None),
+                        }
+                    }
+                    _ => (&parts[1..], None),
+                },
+            };
+            match month.unwrap_or(Ok((today.year(), today.month()))) {
+                _ if words.is_empty() => {
+                    println!("{}", usage("calendar <habit_name> [-N|+N|YYYY-MM] [--browse]"));
+                    println!("{}", t!("common.examples", examples = "calendar Workout -2, calendar Workout 2025-03").paint(Role::Muted));
                 }
+                Ok(month) => tracker.show_calendar(&words.join(" "), month, command.has_flag("browse"), format),
+                Err(arg) => fail(t!("calendar.invalid_month", value = arg)),
             }
-            "remind" | "reminder" => {
-                if parts.len() >= 3 {
-                    let name = parts[1].to_string();
-                    let time = parts[2].to_string();
-                    tracker.set_reminder(&name, time);
-                    tracker.save().unwrap();
-                } else {
-                    println!("{}", "Usage: remind <habit_name> <HH:MM>".yellow());
+        }
+        "grid" => {
+            let monthly = parts.get(1) == Some(&"month");
+            let args = if matches!(parts.get(1), Some(&"week" | &"month")) { &parts[2..] } else { &parts[1..] };
+            match parse_period(monthly, args) {
+                Some((first, last)) => tracker.show_grid(first, last, monthly, command.flag("category")),
+                None => {
+                    println!("{}", usage("grid [week|month] [-N|+N|YYYY-MM-DD|YYYY-MM] [--category <cat>]"));
+                    println!("{}", t!("common.examples", examples = "grid -1, grid month 2025-03 --category Fitness").paint(Role::Muted));
                 }
             }
-            "check" => {
-                tracker.check_reminders();
+        }
+        "chart" => {
+            match ChartKind::from_flag(command.flag("type")) {
+                _ if parts.len() < 2 => {
+                    println!("{}", usage("chart <habit_name> [--type rate|weekly|heatmap] [--out <file.svg>]"));
+                    println!("{}", t!("common.examples", examples = "chart Workout --out workout.svg, chart Reading --type heatmap").paint(Role::Muted));
+                }
+                // Charts are only drawn as SVG, so a .png name would be misleading.
+                Ok(_) if command.flag("out").is_some_and(|path| !path.to_lowercase().ends_with(".svg")) => {
+                    fail(t!("chart.svg_only", file = command.flag("out").unwrap_or("")));
+                }
+                Ok(kinds) => tracker.write_chart(&parts[1..].join(" "), &kinds, command.flag("out")),
+                Err(msg) => fail(format!("❌ {}", msg)),
             }
-            "help" | "h" => {
-                print_help();
+        }
+        "report" => {
+            // The period comes from the --week or --month value, or the words
+            // after the command: `report --month -1`, `report 2025-03-10`.
+            let monthly = command.has_flag("month");
+            let args = match command.flag(if monthly { "month" } else { "week" }) {
+                Some(arg) => vec![arg],
+                None => parts[1..].to_vec(),
+            };
+            let period = if monthly && command.has_flag("week") { None } else { parse_period(monthly, &args) };
+            match (period, ReportFormat::from_flag(command.flag("format"))) {
+                (None, _) => {
+                    println!("{}", usage("report [--week|--month] [-N|+N|YYYY-MM-DD|YYYY-MM] [--format md|html] [--out <file>]"));
+                    println!("{}", t!("common.examples", examples = "report --week, report --month -1 --format html --out march.html").paint(Role::Muted));
+                }
+                (Some(period), Ok(format)) => {
+                    let path = command.flag("out").map_or_else(|| format!("habit_report.{}", format.extension()), str::to_string);
+                    match report::write(tracker, period, monthly, format, &path) {
+                        Ok(()) => println!("{}", t!("report.done", file = path).paint(Role::Success)),
+                        Err(e) => fail(t!("report.failed", error = e)),
+                    }
+                }
+                (_, Err(msg)) => fail(format!("❌ {}", msg)),
             }
-            "quit" | "exit" | "q" => {
-                println!("{}", "👋 Keep building those habits! Goodbye!".bright_cyan().bold());
-                break;
+        }
+        "heatmap" => {
+            // The year comes from --year or a trailing "2025", unless that
+            // word is part of a habit's name.
+            let (words, year) = match command.flag("year") {
+                Some(arg) => (&parts[1..], Some(arg)),
+                None => match parts[1..].split_last() {
+                    Some((last, rest)) if last.len() == 4 && last.bytes().all(|b| b.is_ascii_digit())
+                        && tracker.find_exact(&parts[1..].join(" ")).is_none() => (rest, Some(*last)),
+                    _ => (&parts[1..], None),
+                },
+            };
+            let name = (!words.is_empty()).then(|| words.join(" "));
+            match year.map(|arg| arg.parse::<i32>().ok().filter(|y| (1000..=9999).contains(y)).ok_or(arg)).transpose() {
+                Ok(year) => tracker.show_heatmap(name.as_deref(), year),
+                Err(arg) => fail(t!("heatmap.invalid_year", value = arg)),
             }
-            _ => {
-                println!("{}", format!("❌ Unknown command: '{}'", parts[0]).red());
-                println!("{}", "Type 'help' for available commands.".bright_black());
+        }
+        "export" => {
+            let filename = if parts.len() > 1 {
+                parts[1].to_string()
+            } else {
+                config::current().export_file
+            };
+            // Calendars have no modes; the file holds completions and reminders.
+            if filename.to_lowercase().ends_with(".ics") {
+                if command.has_flag("mode") {
+                    fail(t!("ics.no_mode"));
+                    return true;
+                }
+                match ics::write(tracker, &filename) {
+                    Ok(()) => println!("{}", t!("export.done", file = filename).paint(Role::Celebrate)),
+                    Err(e) => fail(t!("export.failed", error = e)),
+                }
+                return true;
+            }
+            let mode = match ExportMode::from_flag(command.flag("mode")) {
+                Ok(mode) => mode,
+                Err(msg) => {
+                    fail(format!("❌ {}", msg));
+                    return true;
+                }
+            };
+            match tracker.export_csv(&filename, mode) {
+                Ok(_) => {},
+                Err(e) => fail(t!("export.failed", error = e)),
+            }
+        }
+        "import" => {
+            let options = Strategy::from_flag(command.flag("strategy"))
+                .and_then(|strategy| Ok((strategy, Mapping::from_flag(command.flag("map"))?)));
+            match options {
+                _ if parts.len() < 2 => {
+                    println!("{}", usage("import <file.csv|file.json|loop-backup> [--dry-run] [--strategy merge|skip|replace] [--map field=Column,...]"));
+                    println!("{}", t!("common.examples", examples = "import backup.json --dry-run, import log.csv --map \"date=Day,name=Habit\"").paint(Role::Muted));
+                }
+                Ok((strategy, mapping)) => {
+                    if import::run(tracker, &parts[1..].join(" "), strategy, &mapping, command.has_flag("dry-run")) {
+                        tracker.save_or_report();
+                    }
+                }
+                Err(msg) => fail(format!("❌ {}", msg)),
+            }
+        }
+        "remind" | "reminder" => {
+            if parts.len() >= 3 {
+                let name = parts[1..parts.len() - 1].join(" ");
+                let time = parts[parts.len() - 1].to_string();
+                tracker.set_reminder(&name, time);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("remind <habit_name> <HH:MM>"));
+            }
+        }
+        "pause" => {
+            let dates = match (command.flag("from"), command.flag("to")) {
+                (Some(from), Some(to)) if parts.len() >= 2 => Some((parts[1..].join(" "), from, to)),
+                (None, None) if parts.len() >= 4 => Some((
+                    parts[1..parts.len() - 2].join(" "),
+                    parts[parts.len() - 2],
+                    parts[parts.len() - 1],
+                )),
+                _ => None,
+            };
+            if let Some((target, from, to)) = dates {
+                let from = NaiveDate::parse_from_str(from, "%Y-%m-%d");
+                let to = NaiveDate::parse_from_str(to, "%Y-%m-%d");
+                match (from, to) {
+                    (Ok(from), Ok(to)) if from <= to => {
+                        tracker.pause_habit(&target, from, to);
+                        tracker.save_or_report();
+                    }
+                    (Ok(_), Ok(_)) => {
+                        fail(t!("pause.ends_before_start"));
+                    }
+                    _ => fail(t!("error.date_format")),
+                }
+            } else {
+                println!("{}", t!("pause.usage").paint(Role::Warning));
+                println!("{}", t!("common.example", example = "pause all 2025-07-01 2025-07-14").paint(Role::Muted));
+            }
+        }
+        "resume" | "unpause" => {
+            if parts.len() > 1 {
+                let target = parts[1..].join(" ");
+                tracker.resume_habit(&target);
+                tracker.save_or_report();
+            } else {
+                println!("{}", usage("resume <habit_name|all>"));
+            }
+        }
+        "theme" => show_theme(parts.get(1).copied()),
+        "config" => {
+            let current = config::current();
+            match (parts.get(1).map(|s| s.to_lowercase()).as_deref(), parts.len()) {
+                (None, _) | (Some("list"), 2) => show_config(),
+                (Some("get"), 3) => match current.get(parts[2]) {
+                    Ok(value) => println!("{} = {}", parts[2], value),
+                    Err(msg) => fail(format!("❌ {}", msg)),
+                },
+                (Some("set"), 4..) => update_config(tracker, parts[2], current.set(parts[2], &parts[3..].join(" "))),
+                (Some("unset"), 3) => update_config(tracker, parts[2], current.unset(parts[2])),
+                _ => {
+                    println!("{}", usage("config [get <key> | set <key> <value> | unset <key>]"));
+                    println!("{}", t!("common.example", example = "config set week_start sunday").paint(Role::Muted));
+                }
+            }
+        }
+        "tui" | "dashboard" => {
+            if let Err(e) = tui::run(tracker) {
+                fail(t!("app.dashboard_failed", error = e));
             }
         }
+        "check" => {
+            tracker.check_reminders();
+        }
+        "help" | "h" => {
+            print_help();
+        }
+        "quit" | "exit" | "q" => {
+            println!("{}", t!("app.goodbye").paint(Role::Title));
+            return false;
+        }
+        _ => {
+            fail(t!("app.unknown_command", command = parts[0]));
+            eprintln!("{}", t!("app.help_hint").paint(Role::Muted));
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn habit(name: &str, created: &str, completions: &[&str]) -> Habit {
+        let mut habit = Habit::new(name.to_lowercase(), name.to_string(), GoalType::Daily, None);
+        habit.created_date = date(created);
+        habit.completions = completions.iter().map(|d| date(d)).collect();
+        habit
+    }
+
+    #[test]
+    fn heat_levels_only_count_habits_that_existed() {
+        let old = habit("Old", "2025-01-01", &["2025-03-01"]);
+        // Imported history can reach back before the habit was created.
+        let new = habit("New", "2025-06-01", &["2025-03-01", "2025-06-02"]);
+        let habits = [&old, &new];
+        assert_eq!(heat_level(&habits, date("2025-03-01")), Some(4));
+        assert_eq!(heat_level(&habits, date("2025-06-02")), Some(2));
+        assert_eq!(heat_level(&habits, date("2025-06-03")), Some(0));
+        assert_eq!(heat_level(&habits, date("2024-12-31")), None);
+    }
+
+    #[test]
+    fn streaks_carry_across_pauses() {
+        let mut run = habit("Run", "2025-01-01", &["2025-03-01", "2025-03-02", "2025-03-06", "2025-03-07"]);
+        assert_eq!(run.current_streak_as_of(date("2025-03-07")), 2);
+        assert_eq!(run.longest_streak(), 2);
+        run.pauses.push(PausePeriod { from: date("2025-03-03"), to: date("2025-03-05") });
+        assert_eq!(run.current_streak_as_of(date("2025-03-07")), 4);
+        // Today isn't missed until it's over, and a pause running today counts as kept.
+        assert_eq!(run.current_streak_as_of(date("2025-03-08")), 4);
+        assert_eq!(run.current_streak_as_of(date("2025-03-04")), 2);
+        assert_eq!(run.current_streak_as_of(date("2025-03-09")), 0);
+        assert_eq!(run.longest_streak(), 4);
+    }
+
+    #[test]
+    fn goal_changes_apply_from_their_date() {
+        let mut run = habit("Run", "2025-01-01", &[]);
+        run.goal = GoalType::Weekly(3);
+        // Monday 2025-03-03 to Sunday 2025-03-09, with a new goal from Wednesday.
+        run.set_goal_as_of(GoalType::Weekly(5), date("2025-03-05"), date("2025-03-04"));
+        assert_eq!(run.goal, GoalType::Weekly(3));
+        assert_eq!(run.goal_on(date("2025-03-04")), &GoalType::Weekly(3));
+        assert_eq!(run.goal_on(date("2025-03-05")), &GoalType::Weekly(5));
+        assert_eq!(run.goal_on(date("2024-12-01")), &GoalType::Weekly(3));
+        // 2 days at 3/7 and 5 days at 5/7 come to 31/7, rounded up.
+        assert_eq!(run.progress_between(date("2025-03-03"), date("2025-03-09")), (0, 5));
+
+        // An earlier change replaces the one scheduled after it.
+        run.set_goal_as_of(GoalType::Daily, date("2025-03-04"), date("2025-03-06"));
+        assert_eq!(run.goal, GoalType::Daily);
+        assert_eq!(run.goal_on(date("2025-03-08")), &GoalType::Daily);
+        assert_eq!(run.goal_history.len(), 2);
+        assert_eq!(run.progress_between(date("2025-03-03"), date("2025-03-09")), (0, 7));
+    }
+
+    #[test]
+    fn weeks_follow_week_start() {
+        let mut run = habit("Run", "2025-01-01", &["2025-03-01", "2025-03-02", "2025-03-03", "2025-03-05", "2025-03-06"]);
+        run.goal = GoalType::Weekly(3);
+        // Wednesday; Thursday's completion hasn't happened yet.
+        let today = date("2025-03-05");
+        assert_eq!(run.week_progress_as_of(config::WeekStart::Monday, today), (2, 3));
+        assert_eq!(run.week_progress_as_of(config::WeekStart::Sunday, today), (3, 3));
+        assert_eq!(run.week_progress_as_of(config::WeekStart::Saturday, today), (4, 3));
+        assert_eq!(run.progress_as_of(date("2025-03-01"), date("2025-03-07"), date("2025-03-07")), (5, 3));
+    }
+
+    #[test]
+    fn monthly_goals_count_in_full_over_a_month() {
+        let mut reading = habit("Reading", "2025-01-01", &["2025-03-03", "2025-03-10"]);
+        reading.goal = GoalType::Monthly(10);
+        assert_eq!(reading.progress_between(date("2025-03-01"), date("2025-03-31")), (2, 10));
+        // A week of a monthly habit is still judged day by day.
+        assert_eq!(reading.progress_between(date("2025-03-03"), date("2025-03-09")), (1, 7));
+        reading.pauses.push(PausePeriod { from: date("2025-03-01"), to: date("2025-03-15") });
+        assert_eq!(reading.progress_between(date("2025-03-01"), date("2025-03-31")), (0, 6));
+
+        let mut run = habit("Run", "2025-01-01", &[]);
+        run.goal = GoalType::Weekly(3);
+        assert_eq!(run.progress_between(date("2025-02-01"), date("2025-02-28")), (0, 12));
     }
 }
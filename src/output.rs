@@ -0,0 +1,353 @@
+// Machine-readable output for the query commands (`--format json|tsv`).
+//
+// These structures are an interface for scripts and dashboards: fields may be
+// added over time, but existing ones keep their names and meaning. The layout
+// is documented in the README under "Machine-readable Output".
+
+use crate::i18n::t;
+use crate::{GoalType, Habit, HabitTracker, PausePeriod};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn from_flag(flag: Option<&str>) -> Result<Self, String> {
+        match flag.map(str::to_lowercase).as_deref() {
+            None | Some("text") => Ok(OutputFormat::Text),
+            Some("json") => Ok(OutputFormat::Json),
+            Some("tsv") => Ok(OutputFormat::Tsv),
+            Some(other) => Err(t!("output.unknown_format", format = other)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GoalOut {
+    pub kind: &'static str, // "daily", "weekly" or "monthly"
+    pub count: u32,         // completions per period; 1 for daily
+}
+
+impl From<&GoalType> for GoalOut {
+    fn from(goal: &GoalType) -> Self {
+        match goal {
+            GoalType::Daily => GoalOut { kind: "daily", count: 1 },
+            GoalType::Weekly(n) => GoalOut { kind: "weekly", count: *n },
+            GoalType::Monthly(n) => GoalOut { kind: "monthly", count: *n },
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct HabitSummary {
+    pub id: String,
+    pub name: String,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub goal: GoalOut,
+    pub created_date: NaiveDate,
+    pub done_today: bool,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub week_count: usize,
+    pub week_target: usize,
+    pub week_goal_met: bool,
+    pub month_count: usize,
+    pub total_completions: usize,
+    pub reminder_time: Option<String>,
+    pub paused_until: Option<NaiveDate>,
+    pub archived_date: Option<NaiveDate>,
+}
+
+impl HabitSummary {
+    pub fn new(habit: &Habit) -> Self {
+        let (week_count, week_target) = habit.goal_progress_this_week();
+        HabitSummary {
+            id: habit.id.clone(),
+            name: habit.name.clone(),
+            category: habit.category.clone(),
+            tags: habit.tags.clone(),
+            goal: GoalOut::from(&habit.goal),
+            created_date: habit.created_date,
+            done_today: habit.is_done_today(),
+            current_streak: habit.current_streak(),
+            longest_streak: habit.longest_streak(),
+            week_count,
+            week_target,
+            week_goal_met: week_count >= week_target,
+            month_count: habit.monthly_count(),
+            total_completions: habit.completions.len(),
+            reminder_time: habit.reminder_time.clone(),
+            paused_until: habit.active_pause().map(|p| p.to),
+            archived_date: habit.archived_date,
+        }
+    }
+
+    fn tsv_header() -> Vec<String> {
+        [
+            "id", "name", "category", "tags", "goal_kind", "goal_count", "created_date",
+            "done_today", "current_streak", "longest_streak", "week_count", "week_target",
+            "week_goal_met", "month_count", "total_completions", "reminder_time",
+            "paused_until", "archived_date",
+        ].iter().map(|s| s.to_string()).collect()
+    }
+
+    fn tsv_row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            self.category.clone().unwrap_or_default(),
+            self.tags.join(","),
+            self.goal.kind.to_string(),
+            self.goal.count.to_string(),
+            self.created_date.to_string(),
+            self.done_today.to_string(),
+            self.current_streak.to_string(),
+            self.longest_streak.to_string(),
+            self.week_count.to_string(),
+            self.week_target.to_string(),
+            self.week_goal_met.to_string(),
+            self.month_count.to_string(),
+            self.total_completions.to_string(),
+            self.reminder_time.clone().unwrap_or_default(),
+            optional_date(self.paused_until),
+            optional_date(self.archived_date),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct GoalChangeOut {
+    pub from: NaiveDate,
+    pub goal: GoalOut,
+}
+
+#[derive(Serialize)]
+pub struct HabitDetails {
+    #[serde(flatten)]
+    pub summary: HabitSummary,
+    pub completions: Vec<NaiveDate>,
+    pub goal_history: Vec<GoalChangeOut>,
+    pub pauses: Vec<PausePeriod>,
+}
+
+#[derive(Serialize)]
+pub struct CategorySummary {
+    pub name: String,
+    pub habit_count: usize, // excluding archived habits
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub goal_percent: Option<u32>,
+    pub habits_on_track: Option<usize>, // only set when there is a goal
+    pub habits_counted: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct TagSummary {
+    pub name: String,
+    pub habits: Vec<String>, // IDs of the active habits carrying the tag
+    pub week_count: usize,
+    pub week_target: usize,
+    pub total_completions: usize,
+    pub best_current_streak: u32,
+}
+
+#[derive(Serialize)]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    pub completed: bool,
+    pub paused: bool,
+}
+
+#[derive(Serialize)]
+pub struct CalendarOut {
+    pub id: String,
+    pub name: String,
+    pub year: i32,
+    pub month: u32,
+    pub days: Vec<CalendarDay>,
+}
+
+fn optional_date(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.to_string()).unwrap_or_default()
+}
+
+// Output is usually piped into other tools, so a closed pipe isn't an error.
+fn print_tsv(rows: Vec<Vec<String>>) {
+    let mut out = io::stdout().lock();
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| c.replace(['\t', '\n', '\r'], " ")).collect();
+        if writeln!(out, "{}", cells.join("\t")).is_err() {
+            return;
+        }
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => {
+            let _ = writeln!(io::stdout().lock(), "{}", json);
+        }
+        Err(e) => eprintln!("{}", t!("output.serialize_failed", error = e)),
+    }
+}
+
+#[derive(Serialize)]
+struct HabitList<'a> {
+    habits: &'a [HabitSummary],
+}
+
+#[derive(Serialize)]
+struct CategoryList<'a> {
+    categories: &'a [CategorySummary],
+}
+
+#[derive(Serialize)]
+struct TagList<'a> {
+    tags: &'a [TagSummary],
+}
+
+// `{"habits": [HabitSummary, ...]}`
+pub fn print_habits(habits: &[&Habit], format: OutputFormat) {
+    let summaries: Vec<HabitSummary> = habits.iter().map(|h| HabitSummary::new(h)).collect();
+    if format == OutputFormat::Json {
+        print_json(&HabitList { habits: &summaries });
+    } else {
+        let mut rows = vec![HabitSummary::tsv_header()];
+        rows.extend(summaries.iter().map(HabitSummary::tsv_row));
+        print_tsv(rows);
+    }
+}
+
+// Archived habits in the same layout as `print_habits`, with
+// `archived_date` always set.
+pub fn print_archived(habits: &[&Habit], format: OutputFormat) {
+    print_habits(habits, format);
+}
+
+// A single HabitDetails object. TSV adds a comma-separated `completions` column.
+pub fn print_habit_details(habit: &Habit, format: OutputFormat) {
+    let details = HabitDetails {
+        summary: HabitSummary::new(habit),
+        completions: habit.completions.clone(),
+        goal_history: habit.goal_history.iter()
+            .map(|c| GoalChangeOut { from: c.from, goal: GoalOut::from(&c.goal) })
+            .collect(),
+        pauses: habit.pauses.clone(),
+    };
+    if format == OutputFormat::Json {
+        print_json(&details);
+    } else {
+        let mut header = HabitSummary::tsv_header();
+        header.push("completions".to_string());
+        let mut row = details.summary.tsv_row();
+        row.push(details.completions.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(","));
+        print_tsv(vec![header, row]);
+    }
+}
+
+// `{"categories": [CategorySummary, ...]}`
+pub fn print_categories(tracker: &HabitTracker, format: OutputFormat) {
+    let summaries: Vec<CategorySummary> = tracker.category_names().into_iter()
+        .map(|name| {
+            let settings = tracker.categories.get(&name);
+            let progress = tracker.category_goal_progress(&name);
+            CategorySummary {
+                habit_count: tracker.habits.values()
+                    .filter(|h| !h.is_archived() && h.category.as_deref() == Some(name.as_str()))
+                    .count(),
+                color: settings.and_then(|c| c.color.clone()),
+                icon: settings.and_then(|c| c.icon.clone()),
+                goal_percent: settings.and_then(|c| c.goal_percent),
+                habits_on_track: progress.map(|(met, _, _)| met),
+                habits_counted: progress.map(|(_, total, _)| total),
+                name,
+            }
+        })
+        .collect();
+
+    if format == OutputFormat::Json {
+        print_json(&CategoryList { categories: &summaries });
+    } else {
+        let mut rows = vec![
+            ["name", "habit_count", "color", "icon", "goal_percent", "habits_on_track", "habits_counted"]
+                .iter().map(|s| s.to_string()).collect(),
+        ];
+        let optional = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+        rows.extend(summaries.iter().map(|c| vec![
+            c.name.clone(),
+            c.habit_count.to_string(),
+            c.color.clone().unwrap_or_default(),
+            c.icon.clone().unwrap_or_default(),
+            c.goal_percent.map(|p| p.to_string()).unwrap_or_default(),
+            optional(c.habits_on_track),
+            optional(c.habits_counted),
+        ]));
+        print_tsv(rows);
+    }
+}
+
+// `{"tags": [TagSummary, ...]}`. TSV lists the habit IDs comma-separated.
+pub fn print_tags(tags: &[(String, Vec<&Habit>)], format: OutputFormat) {
+    let summaries: Vec<TagSummary> = tags.iter()
+        .map(|(name, habits)| {
+            let (week_count, week_target) = habits.iter()
+                .map(|h| h.goal_progress_this_week())
+                .fold((0, 0), |(c, t), (hc, ht)| (c + hc, t + ht));
+            TagSummary {
+                name: name.clone(),
+                habits: habits.iter().map(|h| h.id.clone()).collect(),
+                week_count,
+                week_target,
+                total_completions: habits.iter().map(|h| h.completions.len()).sum(),
+                best_current_streak: habits.iter().map(|h| h.current_streak()).max().unwrap_or(0),
+            }
+        })
+        .collect();
+
+    if format == OutputFormat::Json {
+        print_json(&TagList { tags: &summaries });
+    } else {
+        let mut rows = vec![
+            ["name", "habits", "week_count", "week_target", "total_completions", "best_current_streak"]
+                .iter().map(|s| s.to_string()).collect(),
+        ];
+        rows.extend(summaries.iter().map(|t| vec![
+            t.name.clone(),
+            t.habits.join(","),
+            t.week_count.to_string(),
+            t.week_target.to_string(),
+            t.total_completions.to_string(),
+            t.best_current_streak.to_string(),
+        ]));
+        print_tsv(rows);
+    }
+}
+
+// A single CalendarOut object. TSV has one row per day.
+pub fn print_calendar(habit: &Habit, year: i32, month: u32, format: OutputFormat) {
+    let days: Vec<CalendarDay> = (1..=crate::days_in_month(year, month))
+        .map(|day| {
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            CalendarDay {
+                date,
+                completed: habit.completions.contains(&date),
+                paused: habit.is_paused(date),
+            }
+        })
+        .collect();
+
+    if format == OutputFormat::Json {
+        print_json(&CalendarOut { id: habit.id.clone(), name: habit.name.clone(), year, month, days });
+    } else {
+        let mut rows = vec![vec!["date".to_string(), "completed".to_string(), "paused".to_string()]];
+        rows.extend(days.iter().map(|d| vec![d.date.to_string(), d.completed.to_string(), d.paused.to_string()]));
+        print_tsv(rows);
+    }
+}
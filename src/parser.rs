@@ -0,0 +1,282 @@
+// Command-line tokenizer and parser for the interactive prompt.
+//
+// Grammar: `<command> [args...] [--flag value | --flag=value | --switch]...`
+// Arguments may be quoted with "..." or '...'. A backslash escapes the next
+// character outside single quotes. A lone `--` ends option parsing.
+
+use crate::i18n::t;
+
+// Flags that never take a value.
+const SWITCHES: &[&str] = &["browse", "dry-run"];
+
+// Flags whose value may be left out, like `report --month` for the current
+// month next to `report --month -1`.
+const OPTIONAL_VALUES: &[&str] = &["week", "month"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub text: String,
+    pub quoted: bool, // any part of the token was quoted or escaped
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    pub name: String, // lowercased
+    pub args: Vec<Token>,
+    pub flags: Vec<(String, Option<String>)>,
+}
+
+impl Command {
+    pub fn words(&self) -> Vec<&str> {
+        self.args.iter().map(|t| t.text.as_str()).collect()
+    }
+
+    pub fn flag(&self, name: &str) -> Option<&str> {
+        self.flags.iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|(n, _)| n == name)
+    }
+
+    pub fn check_flags(&self, allowed: &[&str]) -> Result<(), String> {
+        for (name, value) in &self.flags {
+            if !allowed.contains(&name.as_str()) {
+                return Err(t!("parser.unknown_option", flag = name, command = self.name));
+            }
+            if value.is_none() && !SWITCHES.contains(&name.as_str()) && !OPTIONAL_VALUES.contains(&name.as_str()) {
+                return Err(t!("parser.needs_value", flag = name));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            return Ok(tokens);
+        }
+
+        let mut text = String::new();
+        let mut quoted = false;
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            match c {
+                '\\' => {
+                    let escaped = chars.next().ok_or_else(|| t!("parser.trailing_backslash"))?;
+                    text.push(escaped);
+                    quoted = true;
+                }
+                '"' | '\'' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            None => return Err(t!("parser.missing_quote", quote = c)),
+                            Some(q) if q == c => break,
+                            Some('\\') if c == '"' => {
+                                text.push(chars.next().ok_or_else(|| t!("parser.missing_quote", quote = '"'))?);
+                            }
+                            Some(other) => text.push(other),
+                        }
+                    }
+                }
+                _ => text.push(c),
+            }
+        }
+        tokens.push(Token { text, quoted });
+    }
+}
+
+// Returns `Ok(None)` for a blank line.
+pub fn parse(input: &str) -> Result<Option<Command>, String> {
+    Ok(parse_tokens(tokenize(input)?))
+}
+
+// Builds a command from process arguments, which the shell has already split.
+// An argument containing whitespace must have been quoted, so it's treated
+// like a quoted token.
+pub fn parse_args(args: &[String]) -> Option<Command> {
+    parse_tokens(args.iter()
+        .map(|a| Token { text: a.clone(), quoted: a.contains(char::is_whitespace) })
+        .collect())
+}
+
+fn parse_tokens(tokens: Vec<Token>) -> Option<Command> {
+    let mut tokens = tokens.into_iter();
+    let first = tokens.next()?;
+
+    let mut command = Command {
+        name: first.text.to_lowercase(),
+        args: Vec::new(),
+        flags: Vec::new(),
+    };
+    let mut tokens = tokens.peekable();
+    let mut options_done = false;
+
+    while let Some(token) = tokens.next() {
+        let flag = (!token.quoted && !options_done)
+            .then(|| token.text.strip_prefix("--"))
+            .flatten();
+        match flag {
+            Some("") => options_done = true,
+            Some(flag) => {
+                if let Some((name, value)) = flag.split_once('=') {
+                    command.flags.push((name.to_lowercase(), Some(value.to_string())));
+                    continue;
+                }
+                let name = flag.to_lowercase();
+                let takes_value = !SWITCHES.contains(&name.as_str())
+                    && tokens.peek().is_some_and(|next| next.quoted || !next.text.starts_with("--"));
+                let value = if takes_value { tokens.next().map(|t| t.text) } else { None };
+                command.flags.push((name, value));
+            }
+            None => command.args.push(token),
+        }
+    }
+    Some(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(input: &str) -> Vec<String> {
+        tokenize(input).unwrap().into_iter().map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(texts("  done   Workout  "), vec!["done", "Workout"]);
+        assert!(texts("   ").is_empty());
+    }
+
+    #[test]
+    fn double_and_single_quotes_group_words() {
+        assert_eq!(texts(r#"add "Read 2 weekly reports" 2 weekly"#), vec!["add", "Read 2 weekly reports", "2", "weekly"]);
+        assert_eq!(texts("category 'Morning Run' Fitness"), vec!["category", "Morning Run", "Fitness"]);
+    }
+
+    #[test]
+    fn quotes_can_join_with_surrounding_text() {
+        assert_eq!(texts(r#"Morning" "Run"#), vec!["Morning Run"]);
+    }
+
+    #[test]
+    fn backslash_escapes() {
+        assert_eq!(texts(r"done Morning\ Run"), vec!["done", "Morning Run"]);
+        assert_eq!(texts(r#"add "Say \"hi\"""#), vec!["add", r#"Say "hi""#]);
+        assert_eq!(texts(r"add 'C:\path'"), vec!["add", r"C:\path"]);
+    }
+
+    #[test]
+    fn quoted_tokens_are_marked() {
+        let tokens = tokenize(r#"a "b" c\ d"#).unwrap();
+        assert_eq!(tokens.iter().map(|t| t.quoted).collect::<Vec<_>>(), vec![false, true, true]);
+    }
+
+    #[test]
+    fn unterminated_quote_is_an_error() {
+        assert!(tokenize(r#"add "Morning Run"#).is_err());
+        assert!(tokenize("add 'x").is_err());
+        assert!(tokenize(r"add x\").is_err());
+    }
+
+    #[test]
+    fn blank_line_parses_to_none() {
+        assert_eq!(parse("   ").unwrap(), None);
+    }
+
+    #[test]
+    fn command_name_is_lowercased() {
+        let command = parse("DONE Workout").unwrap().unwrap();
+        assert_eq!(command.name, "done");
+        assert_eq!(command.words(), vec!["Workout"]);
+    }
+
+    #[test]
+    fn flags_take_the_next_token_as_value() {
+        let command = parse(r#"add "Morning Run" --goal "4 weekly" --category Fitness"#).unwrap().unwrap();
+        assert_eq!(command.words(), vec!["Morning Run"]);
+        assert_eq!(command.flag("goal"), Some("4 weekly"));
+        assert_eq!(command.flag("category"), Some("Fitness"));
+    }
+
+    #[test]
+    fn flags_accept_equals_syntax() {
+        let command = parse("edit Workout --created=2025-01-01").unwrap().unwrap();
+        assert_eq!(command.flag("created"), Some("2025-01-01"));
+        assert_eq!(command.words(), vec!["Workout"]);
+    }
+
+    #[test]
+    fn flag_without_value_before_another_flag() {
+        let command = parse("list --tags --category Fitness").unwrap().unwrap();
+        assert!(command.has_flag("tags"));
+        assert_eq!(command.flag("tags"), None);
+        assert_eq!(command.flag("category"), Some("Fitness"));
+        assert!(command.check_flags(&["tags", "category"]).is_err());
+    }
+
+    #[test]
+    fn quoted_or_escaped_dashes_are_arguments() {
+        let command = parse(r#"add "--weird--" \--odd"#).unwrap().unwrap();
+        assert_eq!(command.words(), vec!["--weird--", "--odd"]);
+        assert!(command.flags.is_empty());
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let command = parse("add -- --not-a-flag").unwrap().unwrap();
+        assert_eq!(command.words(), vec!["--not-a-flag"]);
+    }
+
+    #[test]
+    fn unknown_flags_are_rejected() {
+        let command = parse("done Workout --when today").unwrap().unwrap();
+        assert_eq!(
+            command.check_flags(&[]),
+            Err("Unknown option '--when' for 'done'".to_string())
+        );
+    }
+
+    #[test]
+    fn process_arguments_with_spaces_count_as_quoted() {
+        let args: Vec<String> = ["add", "Read 2 weekly reports", "--goal", "1 weekly"].iter().map(|s| s.to_string()).collect();
+        let command = parse_args(&args).unwrap();
+        assert_eq!(command.words(), vec!["Read 2 weekly reports"]);
+        assert!(command.args[0].quoted);
+        assert_eq!(command.flag("goal"), Some("1 weekly"));
+    }
+
+    #[test]
+    fn optional_values_can_be_left_out() {
+        let command = parse("report --month --format html").unwrap().unwrap();
+        assert!(command.check_flags(&["month", "format"]).is_ok());
+        assert!(command.has_flag("month"));
+        assert_eq!(command.flag("month"), None);
+        assert_eq!(command.flag("format"), Some("html"));
+
+        let command = parse("report --week -1").unwrap().unwrap();
+        assert_eq!(command.flag("week"), Some("-1"));
+        assert!(parse("report --out").unwrap().unwrap().check_flags(&["out"]).is_err());
+    }
+
+    #[test]
+    fn later_flag_wins() {
+        let command = parse("edit x --name a --name b").unwrap().unwrap();
+        assert_eq!(command.flag("name"), Some("b"));
+    }
+}
@@ -0,0 +1,219 @@
+// Terminal rendering. Text output picks a `Role` for each styled piece and
+// this module decides how it looks: the theme supplies the colors, and they
+// are switched off in one place for NO_COLOR and piped output, while
+// `--plain` swaps emoji and box-drawing glyphs for ASCII.
+
+use crate::theme::{RoleStyle, Theme, Thresholds};
+use colored::{Color, ColoredString, Colorize};
+use std::io::IsTerminal;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static COLOR: AtomicBool = AtomicBool::new(true);
+static THEME: RwLock<Theme> = RwLock::new(Theme::DEFAULT);
+static THRESHOLDS: RwLock<Thresholds> = RwLock::new(Thresholds::DEFAULT);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Success,   // confirmations, goals met
+    Warning,   // close to a goal, nothing to show
+    Error,     // failures, goals missed
+    Info,      // neutral notices
+    Muted,     // hints, ids, separators
+    Title,     // screen titles
+    Heading,   // sections within a screen
+    Name,      // habit names
+    Value,     // other user data worth picking out
+    Label,     // field labels in stats
+    Celebrate, // completions and finished tasks
+    Group,     // category and tag headings
+    Accent,    // categories and tags
+    Paused,
+    Reminder,
+    Alert,     // reminders that are due now
+    Streak,
+    Record,    // longest streak
+    Done,      // completed days in calendars
+    Today,     // today in calendars
+    Prompt,
+}
+
+impl Role {
+    pub const ALL: [Role; 21] = [
+        Role::Success, Role::Warning, Role::Error, Role::Info, Role::Muted, Role::Title,
+        Role::Heading, Role::Name, Role::Value, Role::Label, Role::Celebrate, Role::Group,
+        Role::Accent, Role::Paused, Role::Reminder, Role::Alert, Role::Streak, Role::Record,
+        Role::Done, Role::Today, Role::Prompt,
+    ];
+
+    // The key used for this role in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Success => "success",
+            Role::Warning => "warning",
+            Role::Error => "error",
+            Role::Info => "info",
+            Role::Muted => "muted",
+            Role::Title => "title",
+            Role::Heading => "heading",
+            Role::Name => "name",
+            Role::Value => "value",
+            Role::Label => "label",
+            Role::Celebrate => "celebrate",
+            Role::Group => "group",
+            Role::Accent => "accent",
+            Role::Paused => "paused",
+            Role::Reminder => "reminder",
+            Role::Alert => "alert",
+            Role::Streak => "streak",
+            Role::Record => "record",
+            Role::Done => "done",
+            Role::Today => "today",
+            Role::Prompt => "prompt",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Role> {
+        Role::ALL.into_iter().find(|r| r.name().eq_ignore_ascii_case(name))
+    }
+}
+
+fn spec(role: Role) -> RoleStyle {
+    THEME.read().unwrap().style(role)
+}
+
+pub fn set_theme(theme: Theme, thresholds: Thresholds) {
+    *THEME.write().unwrap() = theme;
+    *THRESHOLDS.write().unwrap() = thresholds;
+}
+
+// Success, Warning or Error for progress towards a goal, by the configured
+// thresholds.
+pub fn progress_role(percent: u32) -> Role {
+    THRESHOLDS.read().unwrap().role(percent)
+}
+
+pub fn thresholds() -> Thresholds {
+    *THRESHOLDS.read().unwrap()
+}
+
+// Call once at startup, before anything is printed.
+pub fn init(plain: bool) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let color = !plain && !no_color && std::io::stdout().is_terminal();
+    PLAIN.store(plain, Ordering::Relaxed);
+    COLOR.store(color, Ordering::Relaxed);
+    colored::control::set_override(color);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+pub fn colors_enabled() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+// The color and boldness for a role, or none when colors are off. Used by
+// the dashboard, which draws with its own style type.
+pub fn role_style(role: Role) -> RoleStyle {
+    let (color, bold) = spec(role);
+    (color.filter(|_| colors_enabled()), bold)
+}
+
+// Picks between a glyph and its ASCII stand-in for places where the symbol
+// carries meaning, like the done checkbox in `list`.
+pub fn glyph(fancy: &'static str, ascii: &'static str) -> &'static str {
+    if is_plain() { ascii } else { fancy }
+}
+
+// In plain mode, replaces the glyphs that have an ASCII equivalent and drops
+// other symbols (with the spaces after them), since those are decoration in
+// front of text that already says the same thing. Letters in any script are
+// kept.
+pub fn text(s: &str) -> String {
+    if !is_plain() {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        // ℹ is a letter as far as Unicode is concerned.
+        if c.is_ascii() || (c.is_alphanumeric() && c != 'ℹ') {
+            out.push(c);
+            continue;
+        }
+        let ascii = ascii_for(c).unwrap_or("");
+        out.push_str(ascii);
+        while chars.peek().is_some_and(|&next| is_modifier(next) || (ascii.is_empty() && next == ' ')) {
+            chars.next();
+        }
+    }
+    out
+}
+
+fn ascii_for(c: char) -> Option<&'static str> {
+    Some(match c {
+        '❌' => "Error:",
+        '⚠' => "Warning:",
+        '✓' | '✔' => "OK",
+        '✗' | '✘' => "x",
+        '█' => "#",
+        '░' => ".",
+        '•' | '●' | '○' => "-",
+        '→' => "->",
+        '←' => "<-",
+        '↑' => "up",
+        '↓' => "down",
+        '▶' => ">",
+        '…' => "...",
+        '—' | '–' => "-",
+        '“' | '”' => "\"",
+        '‘' | '’' => "'",
+        _ => return None,
+    })
+}
+
+// Variation selectors and joiners that belong to the emoji before them.
+fn is_modifier(c: char) -> bool {
+    matches!(c, '\u{FE0E}' | '\u{FE0F}' | '\u{200D}')
+}
+
+pub trait Paint {
+    fn paint(&self, role: Role) -> String;
+    fn paint_color(&self, color: Color) -> String;
+    fn paint_style(&self, style: RoleStyle) -> String;
+}
+
+impl<T: AsRef<str> + ?Sized> Paint for T {
+    fn paint(&self, role: Role) -> String {
+        self.paint_style(spec(role))
+    }
+
+    // A user-chosen color, such as a category's, shown in bold.
+    fn paint_color(&self, color: Color) -> String {
+        self.paint_style((Some(color), true))
+    }
+
+    fn paint_style(&self, (color, bold): RoleStyle) -> String {
+        apply(text(self.as_ref()), color, bold).to_string()
+    }
+}
+
+fn apply(s: String, color: Option<Color>, bold: bool) -> ColoredString {
+    let styled = match color {
+        Some(color) => s.color(color),
+        None => s.normal(),
+    };
+    if bold { styled.bold() } else { styled }
+}
+
+// The symbol that goes with a `progress_role` status.
+pub fn status_mark(role: Role) -> &'static str {
+    match role {
+        Role::Success => "✓",
+        Role::Warning => "⚠",
+        _ => "○",
+    }
+}
@@ -0,0 +1,147 @@
+// Line editing for the interactive prompt: history that persists across
+// sessions and tab completion of commands, habits, categories and goals.
+
+use crate::render::{Paint, Role};
+use crate::HabitTracker;
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::borrow::Cow;
+
+pub const HISTORY_FILE: &str = ".habit_tracker_history";
+
+const COMMANDS: &[&str] = &[
+    "add", "remove", "done", "list", "stats", "edit", "archive", "unarchive", "archived",
+    "category", "categories", "tag", "untag", "tags", "calendar", "heatmap", "grid", "export", "import",
+    "report", "chart", "remind", "pause", "resume", "theme", "config", "tui", "check", "help", "quit",
+];
+
+// Commands whose first argument is a habit.
+const HABIT_COMMANDS: &[&str] = &[
+    "remove", "rm", "done", "complete", "stats", "edit", "archive", "unarchive", "restore",
+    "category", "cat", "tag", "untag", "calendar", "cal", "heatmap", "chart", "remind", "reminder",
+    "pause", "resume", "unpause",
+];
+
+const GOAL_WORDS: &[&str] = &["daily", "weekly", "monthly", "per", "week", "month"];
+const EDIT_FIELDS: &[&str] = &["name", "goal", "category", "reminder", "created", "from"];
+const CATEGORY_SUBCOMMANDS: &[&str] = &["rename", "merge", "delete", "color", "icon", "goal"];
+
+pub type ReplEditor = Editor<ReplHelper, DefaultHistory>;
+
+#[derive(Default)]
+pub struct ReplHelper {
+    habits: Vec<String>, // names and IDs
+    categories: Vec<String>,
+}
+
+impl ReplHelper {
+    // Called before each prompt so completions reflect the latest data.
+    pub fn refresh(&mut self, tracker: &HabitTracker) {
+        self.habits = tracker.habits.values()
+            .flat_map(|h| [h.name.clone(), h.id.clone()])
+            .collect();
+        self.habits.sort();
+        self.habits.dedup();
+        self.categories = tracker.category_names();
+    }
+
+    fn argument_candidates(&self, command: &str) -> Vec<String> {
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        let mut candidates = Vec::new();
+        if HABIT_COMMANDS.contains(&command) {
+            candidates.extend(self.habits.iter().cloned());
+        }
+        match command {
+            "add" => candidates.extend(words(GOAL_WORDS)),
+            "edit" => {
+                candidates.extend(words(EDIT_FIELDS));
+                candidates.extend(words(GOAL_WORDS));
+                candidates.extend(self.categories.iter().cloned());
+            }
+            "category" | "cat" | "list" | "ls" => candidates.extend(self.categories.iter().cloned()),
+            "categories" | "cats" => {
+                candidates.extend(words(CATEGORY_SUBCOMMANDS));
+                candidates.extend(self.categories.iter().cloned());
+            }
+            "grid" => {
+                candidates.extend(words(&["week", "month"]));
+                candidates.extend(self.categories.iter().cloned());
+            }
+            "pause" | "resume" | "unpause" => candidates.push("all".to_string()),
+            "theme" => candidates.extend(words(crate::theme::PRESETS)),
+            "config" => {
+                candidates.extend(words(&["get", "set", "unset"]));
+                candidates.extend(words(crate::config::KEYS).into_iter().filter(|k| !k.ends_with("<role>")));
+                candidates.extend(Role::ALL.iter().map(|r| format!("theme.colors.{}", r.name())));
+            }
+            _ => {}
+        }
+        candidates
+    }
+}
+
+fn matching(candidates: &[String], prefix: &str) -> Vec<Pair> {
+    let prefix = prefix.to_lowercase();
+    candidates.iter()
+        .filter(|c| c.to_lowercase().starts_with(&prefix))
+        .map(|c| Pair { display: c.clone(), replacement: c.clone() })
+        .collect()
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        let start = line.len() - line.trim_start().len();
+        let Some(command_len) = line[start..].find(char::is_whitespace) else {
+            let commands: Vec<String> = COMMANDS.iter().map(|c| c.to_string()).collect();
+            let mut pairs = matching(&commands, &line[start..]);
+            for pair in &mut pairs {
+                pair.replacement.push(' ');
+            }
+            return Ok((start, pairs));
+        };
+
+        let command = line[start..start + command_len].to_lowercase();
+        let candidates = self.argument_candidates(&command);
+
+        // Try the whole argument first so multi-word habit names complete,
+        // then fall back to the word under the cursor.
+        let rest = &line[start + command_len..];
+        let arg_start = start + command_len + (rest.len() - rest.trim_start().len());
+        let whole = matching(&candidates, &line[arg_start..]);
+        if !whole.is_empty() {
+            return Ok((arg_start, whole));
+        }
+        // Whitespace such as a no-break space is more than one byte long.
+        let word_start = line.rmatch_indices(char::is_whitespace).next().map_or(0, |(i, space)| i + space.len());
+        Ok((word_start, matching(&candidates, &line[word_start..])))
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight_prompt<'b, 's: 'b, 'p: 'b>(&'s self, prompt: &'p str, _default: bool) -> Cow<'b, str> {
+        Cow::Owned(prompt.paint(Role::Prompt))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+pub fn editor() -> rustyline::Result<ReplEditor> {
+    let mut editor = ReplEditor::new()?;
+    editor.set_helper(Some(ReplHelper::default()));
+    // A missing history file just means this is the first session.
+    let _ = editor.load_history(HISTORY_FILE);
+    Ok(editor)
+}
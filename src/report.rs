@@ -0,0 +1,206 @@
+// `report`: a week's or month's progress as a Markdown or HTML document with
+// inline SVG charts, for sharing with people who don't use the tracker. The
+// document holds the same figures as `list` and `stats`, but for the chosen
+// period rather than the current week.
+
+use crate::config;
+use crate::i18n::{self, t, tn};
+use crate::svg::{self, Day};
+use crate::{Habit, HabitTracker};
+use chrono::{Datelike, Local, NaiveDate};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn from_flag(flag: Option<&str>) -> Result<Self, String> {
+        match flag.map(str::to_lowercase).as_deref() {
+            None | Some("md" | "markdown") => Ok(ReportFormat::Markdown),
+            Some("html") => Ok(ReportFormat::Html),
+            Some(other) => Err(t!("report.unknown_format", format = other)),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+// Pieces of a report, written out as Markdown or HTML.
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    List(Vec<String>),
+    Table(Vec<String>, Vec<Vec<String>>),
+    Svg(String),
+}
+
+pub fn write(tracker: &HabitTracker, (first, last): (NaiveDate, NaiveDate), monthly: bool, format: ReportFormat, path: &str) -> io::Result<()> {
+    let title = if monthly {
+        t!("report.month_title", month = i18n::month_name(first.month()), year = first.year())
+    } else {
+        t!("report.week_title", from = config::format_date(first), to = config::format_date(last))
+    };
+    let blocks = build(tracker, first, last);
+    let document = match format {
+        ReportFormat::Markdown => markdown(&title, &blocks),
+        ReportFormat::Html => html(&title, &blocks),
+    };
+    fs::write(path, document)
+}
+
+fn build(tracker: &HabitTracker, first: NaiveDate, last: NaiveDate) -> Vec<Block> {
+    let today = Local::now().naive_local().date();
+    let mut blocks = vec![Block::Paragraph(t!("report.generated", date = config::format_date(today)))];
+    let mut habits: Vec<&Habit> = tracker.habits.values().filter(|h| !h.is_archived()).collect();
+    if habits.is_empty() {
+        blocks.push(Block::Paragraph(t!("report.no_habits")));
+        return blocks;
+    }
+    habits.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+    let days: Vec<NaiveDate> = first.iter_days().take_while(|d| *d <= last).collect();
+    let progress: Vec<(usize, usize)> = habits.iter().map(|h| h.progress_between(first, last)).collect();
+    let met = |&(count, target): &(usize, usize)| count >= target;
+
+    // Overview
+    let completions: usize = progress.iter().map(|&(count, _)| count).sum();
+    let mut overview = vec![
+        tn!("report.habits", habits.len()),
+        tn!("report.completions", completions),
+        t!("report.goals_met", met = progress.iter().filter(|p| met(p)).count(), total = habits.len()),
+    ];
+    if let Some(best) = habits.iter().max_by_key(|h| h.current_streak()).filter(|h| h.current_streak() > 0) {
+        overview.push(t!("report.best_streak", name = best.name, days = tn!("common.days", best.current_streak())));
+    }
+    blocks.push(Block::Heading(2, t!("report.overview")));
+    blocks.push(Block::List(overview));
+
+    blocks.push(Block::Heading(2, t!("report.per_day")));
+    let bars: Vec<(String, usize, bool)> = days.iter()
+        .map(|d| (d.day().to_string(), habits.iter().filter(|h| h.completions.contains(d)).count(), true))
+        .collect();
+    blocks.push(Block::Svg(svg::bar_chart(&bars).to_string()));
+
+    // Categories, with those that have a goal judged against it
+    let mut categories: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, habit) in habits.iter().enumerate() {
+        let name = habit.category.clone().unwrap_or_else(|| t!("list.uncategorized"));
+        categories.entry(name).or_default().push(i);
+    }
+    let rows = categories.iter().map(|(name, members)| {
+        let on_track = members.iter().filter(|&&i| met(&progress[i])).count();
+        let goal = tracker.categories.get(name).and_then(|c| c.goal_percent).map_or_else(
+            || "–".to_string(),
+            |goal| {
+                let mark = if on_track * 100 >= goal as usize * members.len() { "✓" } else { "✗" };
+                format!("{}% {}", goal, mark)
+            },
+        );
+        vec![
+            name.clone(),
+            members.len().to_string(),
+            members.iter().map(|&i| progress[i].0).sum::<usize>().to_string(),
+            format!("{}/{}", on_track, members.len()),
+            goal,
+        ]
+    }).collect();
+    blocks.push(Block::Heading(2, t!("report.categories")));
+    blocks.push(Block::Table(
+        vec![t!("report.category"), t!("report.habit_count"), t!("report.completion_count"),
+            t!("report.on_track"), t!("report.category_goal")],
+        rows,
+    ));
+
+    blocks.push(Block::Heading(2, t!("report.habits_heading")));
+    blocks.push(Block::Paragraph(t!("report.legend")));
+    for (habit, &(count, target)) in habits.iter().zip(&progress) {
+        let status = if days.iter().all(|&d| habit.is_paused(d)) {
+            t!("report.paused")
+        } else if count >= target {
+            t!("report.goal_met", count = count, target = target)
+        } else {
+            t!("report.goal_open", count = count, target = target)
+        };
+        let mut facts = vec![
+            t!("report.goal", goal = habit.goal.display()),
+            status,
+            t!("report.current_streak", days = tn!("common.days", habit.current_streak())),
+            t!("report.longest_streak", days = tn!("common.days", habit.longest_streak())),
+        ];
+        if let Some(category) = &habit.category {
+            facts.push(t!("report.in_category", category = category));
+        }
+        let states: Vec<(NaiveDate, Day)> = days.iter().map(|&d| (d, habit.day_state(d, today))).collect();
+        blocks.push(Block::Heading(3, habit.name.clone()));
+        blocks.push(Block::List(facts));
+        blocks.push(Block::Svg(svg::calendar(&states, config::current().week_start).to_string()));
+    }
+    blocks
+}
+
+fn markdown(title: &str, blocks: &[Block]) -> String {
+    // Pipes would end a table cell early.
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut out = format!("# {}\n", title);
+    for block in blocks {
+        out.push('\n');
+        match block {
+            Block::Heading(level, text) => out.push_str(&format!("{} {}\n", "#".repeat(*level), text)),
+            Block::Paragraph(text) => out.push_str(&format!("{}\n", text)),
+            Block::List(items) => items.iter().for_each(|item| out.push_str(&format!("- {}\n", item))),
+            Block::Table(header, rows) => {
+                out.push_str(&format!("| {} |\n", header.iter().map(|h| cell(h)).collect::<Vec<_>>().join(" | ")));
+                out.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+                for row in rows {
+                    out.push_str(&format!("| {} |\n", row.iter().map(|c| cell(c)).collect::<Vec<_>>().join(" | ")));
+                }
+            }
+            Block::Svg(svg) => out.push_str(&format!("{}\n", svg)),
+        }
+    }
+    out
+}
+
+fn html(title: &str, blocks: &[Block]) -> String {
+    let escape = svg::escape;
+    let mut body = format!("<h1>{}</h1>\n", escape(title));
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => body.push_str(&format!("<h{0}>{1}</h{0}>\n", level, escape(text))),
+            Block::Paragraph(text) => body.push_str(&format!("<p>{}</p>\n", escape(text))),
+            Block::List(items) => {
+                let items: String = items.iter().map(|item| format!("<li>{}</li>", escape(item))).collect();
+                body.push_str(&format!("<ul>{}</ul>\n", items));
+            }
+            Block::Table(header, rows) => {
+                let header: String = header.iter().map(|h| format!("<th>{}</th>", escape(h))).collect();
+                let rows: String = rows.iter()
+                    .map(|row| format!("<tr>{}</tr>", row.iter().map(|c| format!("<td>{}</td>", escape(c))).collect::<String>()))
+                    .collect();
+                body.push_str(&format!("<table><tr>{}</tr>{}</table>\n", header, rows));
+            }
+            Block::Svg(svg) => body.push_str(&format!("<figure>{}</figure>\n", svg)),
+        }
+    }
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        i18n::locale().code(),
+        escape(title),
+        STYLE,
+        body,
+    )
+}
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:760px;margin:2em auto;padding:0 1em;color:#24292f}\
+h1,h2{border-bottom:1px solid #d0d7de;padding-bottom:.3em}\
+table{border-collapse:collapse}th,td{border:1px solid #d0d7de;padding:4px 10px;text-align:left}\
+th{background:#f6f8fa}figure{margin:1em 0}";
@@ -0,0 +1,204 @@
+// Hand-written SVG charts for `report` and `chart`. They use fixed colors
+// and no scripts, web fonts or external files, so they look the same
+// wherever they're opened.
+
+use crate::config::WeekStart;
+use crate::i18n;
+use chrono::{Datelike, NaiveDate};
+use std::fmt::{self, Write};
+
+const DONE: &str = "#2da44e";
+const SHORT: &str = "#aceebb"; // a bar that fell short of its goal
+const MISSED: &str = "#ebedf0";
+const PAUSED: &str = "#b6d4fe";
+const BORDER: &str = "#d0d7de";
+const TEXT: &str = "#57606a";
+const FONT: &str = r#"font-family="sans-serif" font-size="10""#;
+
+// What a day looks like in `calendar`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Day {
+    Done,
+    Missed,
+    Paused,
+    Open, // still to come, or before the habit existed
+}
+
+// A chart's size and the elements that draw it, so it can be written on its
+// own (through Display) or stacked with others by `stack`.
+pub struct Chart {
+    width: usize,
+    height: usize,
+    body: String,
+}
+
+impl fmt::Display for Chart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}</svg>", open(self.width, self.height), self.body)
+    }
+}
+
+// One image with a title and the charts below it, each under its heading.
+pub fn stack(title: &str, charts: &[(String, Chart)]) -> String {
+    let (margin, heading) = (10, 24);
+    let width = charts.iter().map(|(_, c)| c.width).max().unwrap_or(0).max(200) + 2 * margin;
+    let mut body = format!(r##"<text x="{}" y="22" fill="#24292f" font-family="sans-serif" font-size="16" font-weight="bold">{}</text>"##,
+        margin, escape(title));
+    let mut y = 34;
+    for (name, chart) in charts {
+        let _ = write!(body, r##"<text x="{}" y="{}" fill="#24292f" font-family="sans-serif" font-size="12" font-weight="bold">{}</text>"##,
+            margin, y + 14, escape(name));
+        let _ = write!(body, r#"<g transform="translate({},{})">{}</g>"#, margin, y + heading, chart.body);
+        y += heading + chart.height + margin;
+    }
+    format!(r##"{}<rect width="100%" height="100%" fill="#ffffff"/>{}</svg>"##, open(width, y), body)
+}
+
+// Makes text safe inside SVG and HTML.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// Vertical bars, one per label, scaled to the largest value, with the value
+// above each bar. Bars that missed their goal are drawn lighter. Empty
+// labels are left out, for charts with too many bars to label each.
+pub fn bar_chart(bars: &[(String, usize, bool)]) -> Chart {
+    const SLOT: usize = 22;
+    const HEIGHT: usize = 120;
+    let (left, top, bottom) = (8, 14, 18);
+    let width = left * 2 + bars.len().max(1) * SLOT;
+    let max = bars.iter().map(|(_, value, _)| *value).max().unwrap_or(0).max(1);
+
+    let mut svg = String::new();
+    let _ = write!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}"/>"#,
+        left, top + HEIGHT, width - left, top + HEIGHT, BORDER);
+    for (i, (label, value, met)) in bars.iter().enumerate() {
+        let x = left + i * SLOT;
+        let height = value * HEIGHT / max;
+        let y = top + HEIGHT - height;
+        let center = x + SLOT / 2;
+        if height > 0 {
+            let _ = write!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" rx="2" fill="{}"/>"#,
+                x + 3, y, SLOT - 6, height, if *met { DONE } else { SHORT });
+        }
+        let _ = write!(svg, r#"<text x="{}" y="{}" text-anchor="middle" fill="{}" {}>{}</text>"#,
+            center, y.saturating_sub(3).max(9), TEXT, FONT, value);
+        if !label.is_empty() {
+            let _ = write!(svg, r#"<text x="{}" y="{}" text-anchor="middle" fill="{}" {}>{}</text>"#,
+                center, top + HEIGHT + 13, TEXT, FONT, escape(label));
+        }
+    }
+    Chart { width, height: top + HEIGHT + bottom, body: svg }
+}
+
+// A line through percentages from 0 to 100, with grid lines at 0, 50 and
+// 100%. Like `bar_chart`, only non-empty labels are written.
+pub fn line_chart(points: &[(String, u32)]) -> Chart {
+    const STEP: usize = 12;
+    const HEIGHT: usize = 120;
+    let (left, top, bottom) = (34, 8, 18);
+    let width = left + points.len().max(1) * STEP + 8;
+    let x = |i: usize| left + i * STEP + STEP / 2;
+    let y = |percent: u32| top + HEIGHT - percent.min(100) as usize * HEIGHT / 100;
+
+    let mut svg = String::new();
+    for percent in [0, 50, 100] {
+        let _ = write!(svg, r#"<line x1="{0}" y1="{2}" x2="{1}" y2="{2}" stroke="{3}"/>"#, left, width - 8, y(percent), BORDER);
+        let _ = write!(svg, r#"<text x="{}" y="{}" text-anchor="end" fill="{}" {}>{}%</text>"#,
+            left - 4, y(percent) + 3, TEXT, FONT, percent);
+    }
+    let line: Vec<String> = points.iter().enumerate().map(|(i, &(_, percent))| format!("{},{}", x(i), y(percent))).collect();
+    let _ = write!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#, line.join(" "), DONE);
+    for (i, (label, percent)) in points.iter().enumerate() {
+        let _ = write!(svg, r#"<circle cx="{}" cy="{}" r="2.5" fill="{}"><title>{}%</title></circle>"#, x(i), y(*percent), DONE, percent);
+        if !label.is_empty() {
+            let _ = write!(svg, r#"<text x="{}" y="{}" text-anchor="middle" fill="{}" {}>{}</text>"#,
+                x(i), top + HEIGHT + 13, TEXT, FONT, escape(label));
+        }
+    }
+    Chart { width, height: top + HEIGHT + bottom, body: svg }
+}
+
+// A contribution-graph style year: a column per week, a row per weekday in
+// the configured order, and month names above the week holding the 1st.
+pub fn heatmap(days: &[(NaiveDate, Day)], week_start: WeekStart) -> Chart {
+    const CELL: usize = 11;
+    const GAP: usize = 2;
+    let (left, top) = (22, 14);
+    let Some(&(first, _)) = days.first() else {
+        return Chart { width: 0, height: 0, body: String::new() };
+    };
+    let start = week_start.week_of(first);
+    let position = |date: NaiveDate| {
+        let offset = (date - start).num_days() as usize;
+        (left + offset / 7 * (CELL + GAP), top + offset % 7 * (CELL + GAP))
+    };
+    let weeks = days.last().map_or(1, |&(last, _)| (last - start).num_days() as usize / 7 + 1);
+
+    let mut svg = String::new();
+    for (row, day) in week_start.days().into_iter().enumerate().filter(|(row, _)| row % 2 == 0) {
+        let _ = write!(svg, r#"<text x="0" y="{}" fill="{}" {}>{}</text>"#,
+            top + row * (CELL + GAP) + 9, TEXT, FONT, escape(&i18n::weekday_initials(day)));
+    }
+    let mut label_end = 0;
+    for &(date, day) in days {
+        let (x, y) = position(date);
+        if date.day() == 1 && x >= label_end {
+            let name = i18n::format_date(date, "%b");
+            let _ = write!(svg, r#"<text x="{}" y="10" fill="{}" {}>{}</text>"#, x, TEXT, FONT, escape(&name));
+            label_end = x + 3 * (CELL + GAP);
+        }
+        let fill = match day {
+            Day::Done => DONE,
+            Day::Missed => MISSED,
+            Day::Paused => PAUSED,
+            Day::Open => "#ffffff",
+        };
+        let _ = write!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" rx="2" fill="{}" stroke="{}"><title>{}</title></rect>"#,
+            x, y, CELL, CELL, fill, if day == Day::Open { BORDER } else { fill }, date);
+    }
+    Chart { width: left + weeks * (CELL + GAP), height: top + 7 * (CELL + GAP), body: svg }
+}
+
+// A month-style calendar with a column per weekday, in the configured
+// order, and the day of the month in each square.
+pub fn calendar(days: &[(NaiveDate, Day)], week_start: WeekStart) -> Chart {
+    const CELL: usize = 20;
+    const GAP: usize = 3;
+    let top = 14;
+    let Some(&(first, _)) = days.first() else {
+        return Chart { width: 0, height: 0, body: String::new() };
+    };
+    let offset = week_start.offset(first.weekday()) as usize;
+    let rows = (offset + days.len()).div_ceil(7);
+    let width = 7 * (CELL + GAP);
+
+    let mut svg = String::new();
+    for (column, day) in week_start.days().into_iter().enumerate() {
+        let _ = write!(svg, r#"<text x="{}" y="10" text-anchor="middle" fill="{}" {}>{}</text>"#,
+            column * (CELL + GAP) + CELL / 2, TEXT, FONT, escape(&i18n::weekday_initials(day)));
+    }
+    for (i, &(date, day)) in days.iter().enumerate() {
+        let (row, column) = ((offset + i) / 7, (offset + i) % 7);
+        let (x, y) = (column * (CELL + GAP), top + row * (CELL + GAP));
+        let (fill, stroke, text) = match day {
+            Day::Done => (DONE, DONE, "#ffffff"),
+            Day::Missed => (MISSED, MISSED, TEXT),
+            Day::Paused => (PAUSED, PAUSED, TEXT),
+            Day::Open => ("#ffffff", BORDER, BORDER),
+        };
+        let _ = write!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" rx="3" fill="{}" stroke="{}"/>"#,
+            x, y, CELL, CELL, fill, stroke);
+        let _ = write!(svg, r#"<text x="{}" y="{}" text-anchor="middle" fill="{}" {}>{}</text>"#,
+            x + CELL / 2, y + 14, text, FONT, date.day());
+    }
+    Chart { width, height: top + rows * (CELL + GAP), body: svg }
+}
+
+fn open(width: usize, height: usize) -> String {
+    format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#, width, height)
+}
@@ -0,0 +1,165 @@
+// Boolean tag filters for `list --tags`, e.g. "health and (morning or work) and not weekend".
+// NOT binds tighter than AND, which binds tighter than OR; adjacent tags are ANDed.
+
+use crate::i18n::t;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagExpr {
+    Tag(String),
+    Not(Box<TagExpr>),
+    And(Box<TagExpr>, Box<TagExpr>),
+    Or(Box<TagExpr>, Box<TagExpr>),
+}
+
+impl TagExpr {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input);
+        if tokens.is_empty() {
+            return Err(t!("tag_expr.empty"));
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(t!("tag_expr.unexpected", token = token)),
+        }
+    }
+
+    pub fn matches(&self, tags: &[String]) -> bool {
+        match self {
+            TagExpr::Tag(tag) => tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            TagExpr::Not(inner) => !inner.matches(tags),
+            TagExpr::And(a, b) => a.matches(tags) && b.matches(tags),
+            TagExpr::Or(a, b) => a.matches(tags) || b.matches(tags),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Vec<String> {
+    input
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|t| t.eq_ignore_ascii_case(keyword))
+    }
+
+    fn or(&mut self) -> Result<TagExpr, String> {
+        let mut expr = self.and()?;
+        while self.peek_keyword("or") {
+            self.pos += 1;
+            expr = TagExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<TagExpr, String> {
+        let mut expr = self.not()?;
+        loop {
+            if self.peek_keyword("and") {
+                self.pos += 1;
+            } else if self.peek().is_none() || self.peek() == Some(")") || self.peek_keyword("or") {
+                break;
+            }
+            expr = TagExpr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<TagExpr, String> {
+        if self.peek_keyword("not") {
+            self.pos += 1;
+            return Ok(TagExpr::Not(Box::new(self.not()?)));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<TagExpr, String> {
+        let token = self.peek().ok_or_else(|| t!("tag_expr.ends_early"))?.to_string();
+        self.pos += 1;
+        match token.as_str() {
+            "(" => {
+                let expr = self.or()?;
+                if self.peek() != Some(")") {
+                    return Err(t!("tag_expr.missing_paren"));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            ")" => Err(t!("tag_expr.unexpected", token = ")")),
+            t if ["and", "or"].iter().any(|k| t.eq_ignore_ascii_case(k)) => {
+                Err(t!("tag_expr.tag_expected", keyword = t))
+            }
+            _ => Ok(TagExpr::Tag(token)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str) -> Box<TagExpr> {
+        Box::new(TagExpr::Tag(name.to_string()))
+    }
+
+    fn matches(input: &str, tags: &[&str]) -> bool {
+        let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+        TagExpr::parse(input).unwrap().matches(&tags)
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_than_or() {
+        assert_eq!(
+            TagExpr::parse("a or not b and c").unwrap(),
+            TagExpr::Or(tag("a"), Box::new(TagExpr::And(Box::new(TagExpr::Not(tag("b"))), tag("c")))),
+        );
+        assert!(matches("a or b and c", &["a"]));
+        assert!(!matches("a or b and c", &["b"]));
+    }
+
+    #[test]
+    fn adjacent_tags_are_anded() {
+        assert_eq!(TagExpr::parse("health morning").unwrap(), TagExpr::And(tag("health"), tag("morning")));
+        assert_eq!(TagExpr::parse("a b or c").unwrap(), TagExpr::parse("a and b or c").unwrap());
+    }
+
+    #[test]
+    fn parentheses_group() {
+        assert_eq!(TagExpr::parse("(a or b) and c").unwrap(), TagExpr::And(Box::new(TagExpr::Or(tag("a"), tag("b"))), tag("c")));
+        assert!(matches("health and (morning or work) and not weekend", &["Health", "work"]));
+        assert!(!matches("health and (morning or work) and not weekend", &["health", "morning", "weekend"]));
+        assert!(matches("not(a)", &[]));
+    }
+
+    #[test]
+    fn keywords_ignore_case() {
+        assert_eq!(TagExpr::parse("a OR Not b").unwrap(), TagExpr::Or(tag("a"), Box::new(TagExpr::Not(tag("b")))));
+        assert_eq!(TagExpr::parse("a AND b").unwrap(), TagExpr::And(tag("a"), tag("b")));
+    }
+
+    #[test]
+    fn mistakes_are_reported() {
+        assert_eq!(TagExpr::parse("  "), Err(t!("tag_expr.empty")));
+        assert_eq!(TagExpr::parse("and)"), Err(t!("tag_expr.tag_expected", keyword = "and")));
+        assert_eq!(TagExpr::parse("a or"), Err(t!("tag_expr.ends_early")));
+        assert_eq!(TagExpr::parse("(a or b"), Err(t!("tag_expr.missing_paren")));
+        assert_eq!(TagExpr::parse("a)"), Err(t!("tag_expr.unexpected", token = ")")));
+        assert_eq!(TagExpr::parse("()"), Err(t!("tag_expr.unexpected", token = ")")));
+        assert_eq!(TagExpr::parse("not"), Err(t!("tag_expr.ends_early")));
+    }
+}
@@ -0,0 +1,164 @@
+// Color themes and status thresholds. A preset gives every `Role` a style and
+// the config file can override single roles on top of it.
+
+use crate::i18n::t;
+use crate::render::Role;
+use colored::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type RoleStyle = (Option<Color>, bool); // (color, bold)
+
+pub const PRESETS: &[&str] = &["default", "colorblind", "light"];
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    preset: fn(Role) -> RoleStyle,
+    overrides: Vec<(Role, RoleStyle)>,
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme { preset: default_preset, overrides: Vec::new() };
+
+    // `colors` maps role names to styles like "bold bright blue".
+    pub fn new(preset: &str, colors: &BTreeMap<String, String>) -> Result<Theme, String> {
+        let preset = match preset.to_lowercase().as_str() {
+            "default" => default_preset,
+            "colorblind" => colorblind_preset,
+            "light" => light_preset,
+            _ => return Err(t!("theme.unknown", name = preset, presets = PRESETS.join(", "))),
+        };
+        let overrides = colors.iter()
+            .map(|(name, spec)| {
+                let role = Role::from_name(name).ok_or_else(|| {
+                    let names: Vec<&str> = Role::ALL.iter().map(|r| r.name()).collect();
+                    t!("theme.unknown_role", name = name, roles = names.join(", "))
+                })?;
+                let style = parse_style(spec).map_err(|e| t!("theme.bad_color", name = name, error = e))?;
+                Ok((role, style))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Theme { preset, overrides })
+    }
+
+    pub fn style(&self, role: Role) -> RoleStyle {
+        self.overrides.iter()
+            .find(|(r, _)| *r == role)
+            .map_or_else(|| (self.preset)(role), |(_, style)| *style)
+    }
+}
+
+// "bold", "bright blue", "bold red", "#ff8800" or "none" for the terminal's
+// default color.
+pub fn parse_style(spec: &str) -> Result<RoleStyle, String> {
+    let words: Vec<&str> = spec.split_whitespace().collect();
+    let bold = words.iter().any(|w| w.eq_ignore_ascii_case("bold"));
+    let name = words.iter()
+        .filter(|w| !w.eq_ignore_ascii_case("bold"))
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let color = if name.is_empty() || name.eq_ignore_ascii_case("none") {
+        None
+    } else if let Some(hex) = name.strip_prefix('#') {
+        let value = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == 6)
+            .ok_or_else(|| t!("theme.bad_hex", color = name))?;
+        Some(Color::TrueColor { r: (value >> 16) as u8, g: (value >> 8) as u8, b: value as u8 })
+    } else {
+        Some(name.replace('_', " ").parse::<Color>().map_err(|_| t!("theme.unknown_color", color = name))?)
+    };
+    Ok((color, bold))
+}
+
+fn default_preset(role: Role) -> RoleStyle {
+    match role {
+        Role::Success => (Some(Color::Green), false),
+        Role::Warning => (Some(Color::Yellow), false),
+        Role::Error => (Some(Color::Red), false),
+        Role::Info => (Some(Color::Cyan), false),
+        Role::Muted => (Some(Color::BrightBlack), false),
+        Role::Title | Role::Today => (Some(Color::BrightCyan), true),
+        Role::Heading | Role::Name => (Some(Color::BrightWhite), true),
+        Role::Value => (Some(Color::BrightWhite), false),
+        Role::Label => (None, true),
+        Role::Celebrate | Role::Done => (Some(Color::Green), true),
+        Role::Group => (Some(Color::BrightMagenta), true),
+        Role::Accent => (Some(Color::BrightMagenta), false),
+        Role::Paused => (Some(Color::BrightBlue), false),
+        Role::Reminder | Role::Streak => (Some(Color::BrightYellow), false),
+        Role::Alert => (Some(Color::BrightYellow), true),
+        Role::Record | Role::Prompt => (Some(Color::BrightGreen), false),
+    }
+}
+
+// Blue/yellow/magenta instead of green/yellow/red, which are hard to tell
+// apart with the common forms of color blindness.
+fn colorblind_preset(role: Role) -> RoleStyle {
+    match role {
+        Role::Success | Role::Record | Role::Prompt => (Some(Color::BrightBlue), false),
+        Role::Celebrate | Role::Done => (Some(Color::BrightBlue), true),
+        Role::Warning => (Some(Color::BrightYellow), false),
+        Role::Error => (Some(Color::Magenta), false),
+        Role::Paused => (Some(Color::Cyan), false),
+        other => default_preset(other),
+    }
+}
+
+// Darker colors that stay readable on a white or cream background.
+fn light_preset(role: Role) -> RoleStyle {
+    match role {
+        Role::Success | Role::Record | Role::Prompt => (Some(Color::Green), false),
+        Role::Warning | Role::Streak => (Some(Color::Magenta), false),
+        Role::Info | Role::Paused | Role::Reminder => (Some(Color::Blue), false),
+        Role::Muted => (Some(Color::BrightBlack), false),
+        Role::Title | Role::Today => (Some(Color::Blue), true),
+        Role::Heading | Role::Name => (Some(Color::Black), true),
+        Role::Value => (Some(Color::Black), false),
+        Role::Group => (Some(Color::Magenta), true),
+        Role::Accent => (Some(Color::Magenta), false),
+        Role::Alert => (Some(Color::Blue), true),
+        other => default_preset(other),
+    }
+}
+
+// Percentages of a goal at which progress counts as on track (shown like a
+// met goal) or close (shown as a warning). Anything lower is behind.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    pub on_track: u32,
+    pub close: u32,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds::DEFAULT
+    }
+}
+
+impl Thresholds {
+    pub const DEFAULT: Thresholds = Thresholds { on_track: 100, close: 70 };
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.on_track == 0 || self.on_track > 100 {
+            return Err(t!("config.out_of_range", key = "thresholds.on_track", min = 1, max = 100));
+        }
+        if self.close > self.on_track {
+            return Err(t!("theme.close_above_on_track"));
+        }
+        Ok(())
+    }
+
+    pub fn role(&self, percent: u32) -> Role {
+        if percent >= self.on_track {
+            Role::Success
+        } else if percent >= self.close {
+            Role::Warning
+        } else {
+            Role::Error
+        }
+    }
+}
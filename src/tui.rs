@@ -0,0 +1,351 @@
+// Full-screen dashboard: the habit list on the left, stats and a month
+// calendar for the selected habit on the right.
+
+use crate::i18n::{self, t, tn};
+use crate::render::{self, Paint, Role};
+use crate::{calendar_page, config, days_in_month, shift_month, Habit, HabitTracker};
+use chrono::{Datelike, Local, NaiveDate};
+use ratatui::crossterm::cursor::MoveUp;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, Clear, ClearType};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::{self, Write};
+
+struct App {
+    ids: Vec<String>,
+    list: ListState,
+    year: i32,
+    month: u32,
+    status: String,
+}
+
+impl App {
+    fn new(tracker: &HabitTracker) -> Self {
+        let mut habits: Vec<&Habit> = tracker.habits.values().filter(|h| !h.is_archived()).collect();
+        habits.sort_by(|a, b| {
+            (a.category.is_none(), &a.category, &a.name).cmp(&(b.category.is_none(), &b.category, &b.name))
+        });
+
+        let today = Local::now().naive_local().date();
+        let mut list = ListState::default();
+        if !habits.is_empty() {
+            list.select(Some(0));
+        }
+        App {
+            ids: habits.iter().map(|h| h.id.clone()).collect(),
+            list,
+            year: today.year(),
+            month: today.month(),
+            status: String::new(),
+        }
+    }
+
+    fn selected_id(&self) -> Option<&String> {
+        self.list.selected().and_then(|i| self.ids.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.ids.is_empty() {
+            return;
+        }
+        let len = self.ids.len() as isize;
+        let current = self.list.selected().unwrap_or(0) as isize;
+        self.list.select(Some((current + delta).rem_euclid(len) as usize));
+    }
+
+    fn shift_month(&mut self, delta: i32) {
+        (self.year, self.month) = shift_month(self.year, self.month, delta).unwrap_or((self.year, self.month));
+    }
+}
+
+// Dashboard styles come from the same roles as the text output, so NO_COLOR
+// and `--plain` apply here too.
+fn style(role: Role) -> Style {
+    let (color, bold) = render::role_style(role);
+    let style = match color {
+        Some(color) => Style::new().fg(term_color(color)),
+        None => Style::new(),
+    };
+    if bold { style.bold() } else { style }
+}
+
+fn term_color(color: colored::Color) -> Color {
+    use colored::Color as C;
+    match color {
+        C::Black => Color::Black,
+        C::Red => Color::Red,
+        C::Green => Color::Green,
+        C::Yellow => Color::Yellow,
+        C::Blue => Color::Blue,
+        C::Magenta => Color::Magenta,
+        C::Cyan => Color::Cyan,
+        C::White => Color::Gray,
+        C::BrightBlack => Color::DarkGray,
+        C::BrightRed => Color::LightRed,
+        C::BrightGreen => Color::LightGreen,
+        C::BrightYellow => Color::LightYellow,
+        C::BrightBlue => Color::LightBlue,
+        C::BrightMagenta => Color::LightMagenta,
+        C::BrightCyan => Color::LightCyan,
+        C::BrightWhite => Color::White,
+        C::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+pub fn run(tracker: &mut HabitTracker) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, tracker);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, tracker: &mut HabitTracker) -> io::Result<()> {
+    let mut app = App::new(tracker);
+
+    loop {
+        terminal.draw(|frame| draw(frame, tracker, &mut app))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+            KeyCode::Left | KeyCode::Char('h') => app.shift_month(-1),
+            KeyCode::Right | KeyCode::Char('l') => app.shift_month(1),
+            KeyCode::Char('t') => {
+                let today = Local::now().naive_local().date();
+                app.year = today.year();
+                app.month = today.month();
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => toggle_today(tracker, &mut app),
+            _ => {}
+        }
+    }
+}
+
+// `calendar --browse`: redraws the month in place while the arrow keys move
+// between months (left/right) and years (up/down), without leaving the
+// prompt for the full-screen dashboard.
+pub fn browse_calendar(habit: &Habit, mut year: i32, mut month: u32) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let mut lines = 0;
+    loop {
+        if lines > 0 {
+            execute!(stdout, MoveUp(lines), Clear(ClearType::FromCursorDown))?;
+        }
+        let page = format!("{}  {}\n", calendar_page(habit, year, month), render::text(&t!("calendar.browse_keys")).paint(Role::Muted));
+        print!("{}", page);
+        stdout.flush()?;
+        lines = page.matches('\n').count() as u16;
+
+        let delta = match read_key()? {
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('p') => -1,
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('n') => 1,
+            KeyCode::Up | KeyCode::Char('k') => -12,
+            KeyCode::Down | KeyCode::Char('j') => 12,
+            KeyCode::Char('t') => {
+                let today = Local::now().naive_local().date();
+                (year, month) = (today.year(), today.month());
+                0
+            }
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => return Ok(()),
+            _ => 0,
+        };
+        (year, month) = shift_month(year, month, delta).unwrap_or((year, month));
+    }
+}
+
+// Raw mode is only on while waiting, so printing works as usual. Ctrl-C
+// can't interrupt in raw mode, so it comes back as Esc.
+fn read_key() -> io::Result<KeyCode> {
+    terminal::enable_raw_mode()?;
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                break Ok(if ctrl_c { KeyCode::Esc } else { key.code });
+            }
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        }
+    };
+    terminal::disable_raw_mode()?;
+    key
+}
+
+fn toggle_today(tracker: &mut HabitTracker, app: &mut App) {
+    let Some(habit) = app.selected_id().and_then(|id| tracker.habits.get_mut(id)) else {
+        return;
+    };
+    let today = Local::now().naive_local().date();
+    app.status = if habit.unmark_complete(today) {
+        t!("tui.unmarked", name = habit.name)
+    } else {
+        habit.mark_complete(today);
+        t!("tui.marked", name = habit.name)
+    };
+    if let Err(e) = tracker.save() {
+        app.status = t!("tui.save_failed", error = e);
+    }
+}
+
+fn draw(frame: &mut Frame, tracker: &HabitTracker, app: &mut App) {
+    let [main, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [left, right] = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+    let [stats_area, calendar_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(11)]).areas(right);
+
+    let items: Vec<ListItem> = app.ids.iter()
+        .map(|id| {
+            let habit = &tracker.habits[id];
+            let check = if habit.is_done_today() {
+                Span::styled(render::glyph("✓", "x"), style(Role::Done))
+            } else {
+                Span::raw(" ")
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw("["),
+                check,
+                Span::raw("] "),
+                Span::raw(habit.name.clone()).bold(),
+                Span::styled(format!("  {} {}", render::glyph("🔥", "streak"), habit.current_streak()), style(Role::Streak)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(format!(" {} ", t!("tui.habits"))))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(render::glyph("▶ ", "> "));
+    frame.render_stateful_widget(list, left, &mut app.list);
+
+    match app.selected_id().map(|id| &tracker.habits[id]) {
+        Some(habit) => {
+            frame.render_widget(
+                Paragraph::new(stats_lines(habit)).block(Block::bordered().title(format!(" {} ", habit.name))),
+                stats_area,
+            );
+            draw_calendar(frame, habit, app.year, app.month, calendar_area);
+        }
+        None => frame.render_widget(
+            Paragraph::new(t!("tui.empty"))
+                .block(Block::bordered().title(format!(" {} ", t!("tui.stats")))),
+            right,
+        ),
+    }
+
+    let help = render::text(&t!("tui.help"));
+    let footer_line = if app.status.is_empty() {
+        Line::from(Span::styled(help, style(Role::Muted)))
+    } else {
+        Line::from(vec![
+            Span::styled(help, style(Role::Muted)),
+            Span::styled("  |  ", style(Role::Muted)),
+            Span::styled(app.status.clone(), style(Role::Info)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(footer_line), footer);
+}
+
+fn stats_lines(habit: &Habit) -> Vec<Line<'static>> {
+    let field = |key: &str, value: String| {
+        Line::from(vec![Span::raw(format!("{:<18}", t!(key))).bold(), Span::raw(value)])
+    };
+
+    let (current, target) = habit.goal_progress_this_week();
+    let rate = (current as f32 / target.max(1) as f32 * 100.0) as u32;
+    let rate_role = render::progress_role(rate);
+
+    let mut lines = vec![
+        field("tui.goal", habit.goal.display()),
+        field("tui.current_streak", tn!("common.days", habit.current_streak())),
+        field("tui.longest_streak", tn!("common.days", habit.longest_streak())),
+        field("tui.created", config::format_date(habit.created_date)),
+        field("tui.total", habit.completions.len().to_string()),
+        field("tui.last_7_days", habit.weekly_count().to_string()),
+        field("tui.last_30_days", habit.monthly_count().to_string()),
+        Line::from(vec![
+            Span::raw(format!("{:<18}", t!("tui.this_week"))).bold(),
+            Span::styled(format!("{}/{} ({}%)", current, target, rate), style(rate_role)),
+        ]),
+    ];
+    if let Some(ref cat) = habit.category {
+        lines.push(field("tui.category", cat.clone()));
+    }
+    if !habit.tags.is_empty() {
+        lines.push(field("tui.tags", habit.tags.join(", ")));
+    }
+    if let Some(ref time) = habit.reminder_time {
+        lines.push(field("tui.reminder", time.clone()));
+    }
+    if let Some(pause) = habit.active_pause() {
+        lines.push(field("tui.paused", format!("{} {} {}", config::format_date(pause.from), render::glyph("→", "->"), config::format_date(pause.to))));
+    }
+    lines
+}
+
+fn draw_calendar(frame: &mut Frame, habit: &Habit, year: i32, month: u32, area: Rect) {
+    let today = Local::now().naive_local().date();
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let week_start = config::current().week_start;
+    let header: String = week_start.days().into_iter().map(|d| format!("{} ", i18n::weekday_initials(d))).collect();
+
+    let mut lines = vec![
+        Line::from(format!("{} {}", i18n::month_name(month), year)).bold().centered(),
+        Line::from(Span::styled(header, style(Role::Muted))).centered(),
+    ];
+    // Each day is two spans (number, gap) so a full week is 14 spans.
+    let blank = || [Span::raw("  "), Span::raw(" ")];
+    let mut week: Vec<Span> = (0..week_start.offset(first_day.weekday())).flat_map(|_| blank()).collect();
+
+    for day in 1..=days_in_month(year, month) {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let mut day_style = if habit.completions.contains(&date) {
+            style(Role::Done)
+        } else if habit.is_paused(date) {
+            style(Role::Paused)
+        } else if date == habit.created_date {
+            style(Role::Accent)
+        } else if date > today || date < habit.created_date {
+            style(Role::Muted)
+        } else {
+            Style::new()
+        };
+        if date == today {
+            day_style = day_style.add_modifier(Modifier::REVERSED);
+        }
+        week.push(Span::styled(format!("{:2}", day), day_style));
+        // Without colors, completed and paused days get a marker instead.
+        let marker = match (render::colors_enabled(), habit.completions.contains(&date), habit.is_paused(date)) {
+            (false, true, _) => "*",
+            (false, false, true) => "-",
+            _ => " ",
+        };
+        week.push(Span::raw(marker));
+
+        if week.len() == 14 {
+            lines.push(Line::from(std::mem::take(&mut week)).centered());
+        }
+    }
+    if !week.is_empty() {
+        // Pad the last week so it lines up with the centered rows above.
+        while week.len() < 14 {
+            week.extend(blank());
+        }
+        lines.push(Line::from(week).centered());
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(render::text(&format!(" {} ", t!("tui.calendar"))))),
+        area,
+    );
+}